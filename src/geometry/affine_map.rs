@@ -0,0 +1,72 @@
+use super::{LinearMap, RealPoint};
+
+// A linear map followed by a translation
+#[derive(Clone, PartialEq, Debug)]
+pub struct AffineMap<const DIM: usize> {
+    linear_map: LinearMap<DIM>,
+    offset: RealPoint<DIM>
+}
+
+impl<const DIM: usize> AffineMap<DIM> {
+    pub fn new(linear_map: LinearMap<DIM>, offset: RealPoint<DIM>) -> Self {
+        Self { linear_map, offset }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(LinearMap::identity(), RealPoint::<DIM>::origin())
+    }
+
+    pub fn translation(offset: RealPoint<DIM>) -> Self {
+        Self::new(LinearMap::identity(), offset)
+    }
+
+    pub fn linear_map(&self) -> &LinearMap<DIM> {
+        &self.linear_map
+    }
+
+    pub fn offset(&self) -> RealPoint<DIM> {
+        self.offset
+    }
+
+    pub fn apply(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        self.linear_map.apply(point) + self.offset
+    }
+
+    // The map that applies `self` and then `next`
+    pub fn compose(&self, next: &Self) -> Self {
+        Self::new(self.linear_map.compose(&next.linear_map), next.apply(self.offset))
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let linear_map = self.linear_map.inverse()?;
+        let offset = linear_map.apply(self.offset) * -1.0;
+
+        Some(Self::new(linear_map, offset))
+    }
+}
+
+impl<const DIM: usize> From<LinearMap<DIM>> for AffineMap<DIM> {
+    fn from(linear_map: LinearMap<DIM>) -> Self {
+        Self::new(linear_map, RealPoint::<DIM>::origin())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{EuclideanMetric, LinearMap, RealPoint};
+    use super::AffineMap;
+
+    #[test]
+    fn compose_and_invert() {
+        let point = RealPoint::<2>::new([-1.0, 2.0]);
+
+        let rotate = AffineMap::from(LinearMap::<2>::rotation(std::f64::consts::FRAC_PI_2));
+        let translate = AffineMap::translation(RealPoint::<2>::new([3.0, 0.5]));
+        let combined = rotate.compose(&translate);
+
+        let distance = |lhs: RealPoint<2>, rhs: RealPoint<2>| (lhs - rhs).magnitude::<EuclideanMetric>();
+
+        assert!(distance(combined.apply(point), RealPoint::<2>::new([1.0, -0.5])) < 1e-12);
+        assert!(distance(combined.inverse().unwrap().apply(combined.apply(point)), point) < 1e-12);
+    }
+}
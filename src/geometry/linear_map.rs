@@ -10,14 +10,160 @@ impl<const DIM: usize> LinearMap<DIM> {
         Self { rows: rows.map(|row| row.into()) }
     }
 
+    pub fn identity() -> Self {
+        Self::scale([1.0; DIM])
+    }
+
+    // Scales each axis by its own factor
+    pub fn scale<T: Into<RealPoint<DIM>>>(factors: T) -> Self {
+        let factors = factors.into();
+        let mut rows = [RealPoint::<DIM>::origin(); DIM];
+
+        for (dim, row) in rows.iter_mut().enumerate() {
+            row[dim] = factors[dim];
+        };
+
+        Self { rows }
+    }
+
+    // Rotates by `angle` radians within the plane of two axes, turning `from_axis` towards
+    // `to_axis`. Any N-D rotation can be composed from these
+    pub fn givens(from_axis: usize, to_axis: usize, angle: f64) -> Self {
+        assert!(from_axis < DIM && to_axis < DIM && from_axis != to_axis, "givens rotations need two distinct axes");
+
+        let (sin, cos) = angle.sin_cos();
+        let mut linear_map = Self::identity();
+
+        linear_map.rows[from_axis][from_axis] = cos;
+        linear_map.rows[from_axis][to_axis] = -sin;
+        linear_map.rows[to_axis][from_axis] = sin;
+        linear_map.rows[to_axis][to_axis] = cos;
+        linear_map
+    }
+
     pub fn apply(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
         RealPoint::<DIM>::new(self.rows.map(|row| row.dot_product(point)))
     }
+
+    // Applies the transpose of the map, which carries gradients back through `apply`
+    pub fn apply_transpose(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        let mut result = RealPoint::<DIM>::origin();
+
+        for (row, &coord) in self.rows.iter().zip(point.iter()) {
+            result += *row * coord;
+        };
+
+        result
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut rows = [RealPoint::<DIM>::origin(); DIM];
+
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            for (col_idx, &value) in row.iter().enumerate() {
+                rows[col_idx][row_idx] = value;
+            };
+        };
+
+        Self { rows }
+    }
+
+    // The map that applies `self` and then `next`
+    pub fn compose(&self, next: &Self) -> Self {
+        Self { rows: next.rows.map(|row| self.apply_transpose(row)) }
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let mut rows = self.rows;
+        let mut determinant = 1.0;
+
+        for dim in 0..DIM {
+            let pivot = match pivot_row(&rows, dim) {
+                Some(pivot) => pivot,
+                None => return 0.0
+            };
+
+            if pivot != dim {
+                rows.swap(pivot, dim);
+                determinant = -determinant;
+            };
+
+            determinant *= rows[dim][dim];
+
+            for row_idx in (dim + 1)..DIM {
+                let factor = rows[row_idx][dim] / rows[dim][dim];
+                let pivot_row = rows[dim];
+
+                rows[row_idx] -= pivot_row * factor;
+            };
+        };
+
+        determinant
+    }
+
+    // Gauss-Jordan elimination with partial pivoting, or `None` for singular maps
+    pub fn inverse(&self) -> Option<Self> {
+        let mut rows = self.rows;
+        let mut inverse = Self::identity().rows;
+
+        for dim in 0..DIM {
+            let pivot = pivot_row(&rows, dim)?;
+
+            rows.swap(pivot, dim);
+            inverse.swap(pivot, dim);
+
+            let scale = rows[dim][dim].recip();
+
+            rows[dim] *= scale;
+            inverse[dim] *= scale;
+
+            for row_idx in (0..DIM).filter(|&row_idx| row_idx != dim) {
+                let factor = rows[row_idx][dim];
+                let (pivot_row, pivot_inverse) = (rows[dim], inverse[dim]);
+
+                rows[row_idx] -= pivot_row * factor;
+                inverse[row_idx] -= pivot_inverse * factor;
+            };
+        };
+
+        Some(Self { rows: inverse })
+    }
+}
+
+impl LinearMap<2> {
+    // Rotates counterclockwise by `angle` radians
+    pub fn rotation(angle: f64) -> Self {
+        Self::givens(0, 1, angle)
+    }
+}
+
+impl LinearMap<3> {
+    // Rotates by `angle` radians about `axis`, counterclockwise when looking down the axis
+    // towards the origin (Rodrigues' formula)
+    pub fn axis_angle(axis: RealPoint<3>, angle: f64) -> Self {
+        let axis = axis.normalize();
+        let (sin, cos) = angle.sin_cos();
+        let (x, y, z) = (axis[0], axis[1], axis[2]);
+        let t = 1.0 - cos;
+
+        Self::new([
+            [t * x * x + cos,     t * x * y - sin * z, t * x * z + sin * y],
+            [t * x * y + sin * z, t * y * y + cos,     t * y * z - sin * x],
+            [t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos    ]
+        ])
+    }
+}
+
+// The row at or below `dim` with the largest entry in column `dim`, if that entry isn't zero
+fn pivot_row<const DIM: usize>(rows: &[RealPoint<DIM>; DIM], dim: usize) -> Option<usize> {
+    let pivot = (dim..DIM).max_by(|&lhs, &rhs| rows[lhs][dim].abs().total_cmp(&rows[rhs][dim].abs()))?;
+
+    if rows[pivot][dim] == 0.0 { None } else { Some(pivot) }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::geometry::RealPoint;
+    use crate::geometry::{EuclideanMetric, RealPoint};
     use super::LinearMap;
 
     #[test]
@@ -32,4 +178,42 @@ mod test {
 
         assert_eq!(linear_map.apply(point), result);
     }
+
+    #[test]
+    fn apply_transpose() {
+        let point = RealPoint::<2>::new([-1.0, 2.0]);
+        let linear_map = LinearMap::<2>::new([
+            [ 1.0, 3.0],
+            [-2.0, 0.0]
+        ]);
+
+        let result = RealPoint::<2>::new([-5.0, -3.0]);
+
+        assert_eq!(linear_map.apply_transpose(point), result);
+    }
+
+    #[test]
+    fn compose_and_invert() {
+        let point = RealPoint::<3>::new([-1.0, 2.0, 0.5]);
+        let linear_map = LinearMap::<3>::new([
+            [ 1.0, 3.0, 0.0],
+            [-2.0, 0.0, 1.0],
+            [ 0.0, 4.0, 2.0]
+        ]);
+
+        let rotation = LinearMap::<3>::axis_angle(RealPoint::<3>::new([1.0, 1.0, 0.0]), 0.7);
+        let inverse = linear_map.inverse().unwrap();
+
+        let distance = |lhs: RealPoint<3>, rhs: RealPoint<3>| (lhs - rhs).magnitude::<EuclideanMetric>();
+
+        assert!((linear_map.determinant() - 8.0).abs() < 1e-12);
+        assert!((rotation.determinant() - 1.0).abs() < 1e-12);
+
+        assert!(distance(linear_map.compose(&rotation).apply(point), rotation.apply(linear_map.apply(point))) < 1e-12);
+        assert!(distance(linear_map.compose(&inverse).apply(point), point) < 1e-12);
+        assert!(distance(rotation.transpose().apply(point), rotation.apply_transpose(point)) < 1e-12);
+        assert!(distance(rotation.apply(RealPoint::<3>::new([1.0, 1.0, 0.0])), RealPoint::<3>::new([1.0, 1.0, 0.0])) < 1e-12);
+
+        assert_eq!(LinearMap::<2>::scale([1.0, 0.0]).inverse(), None);
+    }
 }
@@ -1,9 +1,11 @@
+mod affine_map;
 mod distance_metric;
 mod lattice_point;
 mod linear_map;
 mod real_point;
 mod neighborhood;
 
+pub use affine_map::AffineMap;
 pub use distance_metric::{DistanceMetric, ChebyshevMetric, EuclideanMetric, ManhattanMetric, MinkowskiMetric};
 pub use lattice_point::LatticePoint;
 pub use linear_map::LinearMap;
@@ -0,0 +1,22 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+#[derive(Clone)]
+pub struct AbsNode<const DIM: usize, Source>
+where Source: NoiseNode<DIM> {
+    source: Source
+}
+
+impl<const DIM: usize, Source> AbsNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    pub fn new(source: Source) -> Self {
+        Self { source }
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for AbsNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        self.source.value_at(point).abs()
+    }
+}
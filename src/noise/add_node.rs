@@ -0,0 +1,33 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+#[derive(Clone)]
+pub struct AddNode<const DIM: usize, Lhs, Rhs>
+where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
+    lhs: Lhs,
+    rhs: Rhs
+}
+
+impl<const DIM: usize, Lhs, Rhs> AddNode<DIM, Lhs, Rhs>
+where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
+    pub fn new(lhs: Lhs, rhs: Rhs) -> Self {
+        Self { lhs, rhs }
+    }
+}
+
+impl<const DIM: usize, Lhs, Rhs> NoiseNode<DIM> for AddNode<DIM, Lhs, Rhs>
+where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let lhs_value = self.lhs.value_at(point);
+        let rhs_value = self.rhs.value_at(point);
+
+        lhs_value + rhs_value
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (lhs_value, lhs_gradient) = self.lhs.value_and_gradient(point);
+        let (rhs_value, rhs_gradient) = self.rhs.value_and_gradient(point);
+
+        (lhs_value + rhs_value, lhs_gradient + rhs_gradient)
+    }
+}
@@ -0,0 +1,234 @@
+use crate::geometry::RealPoint;
+use crate::utils;
+use super::NoiseNode;
+
+// Photoshop-style blend modes, combining a base value with a layer painted over it. Both run over
+// [0.0, 1.0], and so does the result
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BlendMode {
+    Multiply,
+    Screen,
+    Overlay,
+    SoftLight,
+    HardLight,
+    Difference,
+    Exclusion,
+    ColorDodge,
+    ColorBurn,
+    LinearDodge,
+    LinearBurn,
+    VividLight,
+    PinLight,
+    Darken,
+    Lighten
+}
+
+impl BlendMode {
+    pub fn apply(self, base: f64, layer: f64) -> f64 {
+        match self {
+            BlendMode::Multiply => base * layer,
+            BlendMode::Screen => 1.0 - (1.0 - base) * (1.0 - layer),
+            BlendMode::Overlay => BlendMode::HardLight.apply(layer, base),
+            BlendMode::SoftLight => {
+                if layer <= 0.5 {
+                    let base_curve = base * (1.0 - base);
+
+                    layer.mul_add(-2.0, 1.0).mul_add(-base_curve, base)
+                } else {
+                    let g_value = if base <= 0.25 {
+                        base.mul_add(16.0, -12.0).mul_add(base, 4.0) * base
+                    } else {
+                        base.sqrt()
+                    };
+
+                    layer.mul_add(2.0, -1.0).mul_add(g_value - base, base)
+                }
+            },
+            BlendMode::HardLight => {
+                if layer < 0.5 {
+                    2.0 * layer * base
+                } else {
+                    let value = (1.0 - layer) * (1.0 - base);
+
+                    value.mul_add(-2.0, 1.0)
+                }
+            },
+            BlendMode::Difference => (base - layer).abs(),
+            BlendMode::Exclusion => (-2.0 * base).mul_add(layer, base + layer),
+            BlendMode::ColorDodge => {
+                if base <= 0.0 {
+                    0.0
+                } else if layer >= 1.0 {
+                    1.0
+                } else {
+                    (base / (1.0 - layer)).min(1.0)
+                }
+            },
+            BlendMode::ColorBurn => {
+                if base >= 1.0 {
+                    1.0
+                } else if layer <= 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - base) / layer).min(1.0)
+                }
+            },
+            BlendMode::LinearDodge => (base + layer).min(1.0),
+            BlendMode::LinearBurn => (base + layer - 1.0).max(0.0),
+            BlendMode::VividLight => {
+                if layer <= 0.5 {
+                    BlendMode::ColorBurn.apply(base, 2.0 * layer)
+                } else {
+                    BlendMode::ColorDodge.apply(base, layer.mul_add(2.0, -1.0))
+                }
+            },
+            BlendMode::PinLight => {
+                if layer <= 0.5 {
+                    base.min(2.0 * layer)
+                } else {
+                    base.max(layer.mul_add(2.0, -1.0))
+                }
+            },
+            BlendMode::Darken => base.min(layer),
+            BlendMode::Lighten => base.max(layer)
+        }
+    }
+}
+
+// Fades a blended value back towards the base. Full opacity returns the blend untouched, rather
+// than lerping it to within rounding of itself
+pub fn apply_opacity(opacity: f64, base: f64, blended: f64) -> f64 {
+    if opacity == 1.0 { blended } else { utils::lerp(opacity, base, blended) }
+}
+
+#[derive(Clone)]
+pub struct BlendNode<const DIM: usize, Base, Layer>
+where Base: NoiseNode<DIM>, Layer: NoiseNode<DIM> {
+    base: Base,
+    layer: Layer,
+    mode: BlendMode,
+    opacity: f64
+}
+
+impl<const DIM: usize, Base, Layer> BlendNode<DIM, Base, Layer>
+where Base: NoiseNode<DIM>, Layer: NoiseNode<DIM> {
+    pub fn new(base: Base, layer: Layer, mode: BlendMode) -> Self {
+        Self { base, layer, mode, opacity: 1.0 }
+    }
+
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity;
+        self
+    }
+}
+
+impl<const DIM: usize, Base, Layer> NoiseNode<DIM> for BlendNode<DIM, Base, Layer>
+where Base: NoiseNode<DIM>, Layer: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let base_value = self.base.value_at(point);
+        let layer_value = self.layer.value_at(point);
+
+        apply_opacity(self.opacity, base_value, self.mode.apply(base_value, layer_value))
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::{ConstNode, NoiseNode, OverlayNode, ScreenNode, SoftLightNode};
+    use super::{BlendMode, BlendNode};
+
+    const SAMPLES: [f64; 7] = [0.0, 0.1, 0.25, 0.5, 0.6, 0.9, 1.0];
+
+    // Textbook formulas, written independently of `BlendMode::apply`
+    fn reference(mode: BlendMode, a: f64, b: f64) -> f64 {
+        let color_dodge = |a: f64, b: f64| if a == 0.0 { 0.0 } else if b == 1.0 { 1.0 } else { f64::min(1.0, a / (1.0 - b)) };
+        let color_burn = |a: f64, b: f64| if a == 1.0 { 1.0 } else if b == 0.0 { 0.0 } else { 1.0 - f64::min(1.0, (1.0 - a) / b) };
+        let hard_light = |a: f64, b: f64| if b < 0.5 { 2.0 * a * b } else { 1.0 - 2.0 * (1.0 - a) * (1.0 - b) };
+
+        match mode {
+            BlendMode::Multiply => a * b,
+            BlendMode::Screen => a + b - a * b,
+            BlendMode::Overlay => if a < 0.5 { 2.0 * a * b } else { 1.0 - 2.0 * (1.0 - a) * (1.0 - b) },
+            BlendMode::SoftLight => {
+                if b <= 0.5 {
+                    a - (1.0 - 2.0 * b) * a * (1.0 - a)
+                } else {
+                    let d = if a <= 0.25 { ((16.0 * a - 12.0) * a + 4.0) * a } else { a.sqrt() };
+
+                    a + (2.0 * b - 1.0) * (d - a)
+                }
+            },
+            BlendMode::HardLight => hard_light(a, b),
+            BlendMode::Difference => (a - b).abs(),
+            BlendMode::Exclusion => a + b - 2.0 * a * b,
+            BlendMode::ColorDodge => color_dodge(a, b),
+            BlendMode::ColorBurn => color_burn(a, b),
+            BlendMode::LinearDodge => f64::min(1.0, a + b),
+            BlendMode::LinearBurn => f64::max(0.0, a + b - 1.0),
+            BlendMode::VividLight => {
+                if b <= 0.5 {
+                    if a == 1.0 { 1.0 } else if b == 0.0 { 0.0 } else { f64::max(0.0, 1.0 - (1.0 - a) / (2.0 * b)) }
+                } else if a == 0.0 {
+                    0.0
+                } else if b == 1.0 {
+                    1.0
+                } else {
+                    f64::min(1.0, a / (2.0 * (1.0 - b)))
+                }
+            },
+            BlendMode::PinLight => if b <= 0.5 { a.min(2.0 * b) } else { a.max(2.0 * b - 1.0) },
+            BlendMode::Darken => a.min(b),
+            BlendMode::Lighten => a.max(b)
+        }
+    }
+
+    #[test]
+    fn matches_reference() {
+        let modes = [
+            BlendMode::Multiply, BlendMode::Screen, BlendMode::Overlay, BlendMode::SoftLight,
+            BlendMode::HardLight, BlendMode::Difference, BlendMode::Exclusion, BlendMode::ColorDodge,
+            BlendMode::ColorBurn, BlendMode::LinearDodge, BlendMode::LinearBurn, BlendMode::VividLight,
+            BlendMode::PinLight, BlendMode::Darken, BlendMode::Lighten
+        ];
+
+        for mode in modes {
+            for a in SAMPLES {
+                for b in SAMPLES {
+                    let value = mode.apply(a, b);
+
+                    assert!((value - reference(mode, a, b)).abs() < 1e-12, "{:?}({}, {}) = {}", mode, a, b, value);
+                    assert!((0.0..=1.0).contains(&value), "{:?}({}, {}) = {}", mode, a, b, value);
+                };
+            };
+        };
+    }
+
+    #[test]
+    fn opacity() {
+        let point = RealPoint::<1>::new([0.0]);
+
+        for a in SAMPLES {
+            for b in SAMPLES {
+                let blend = |opacity: f64| BlendNode::new(ConstNode::new(a), ConstNode::new(b), BlendMode::Overlay).with_opacity(opacity).value_at(point);
+                let expected = a + 0.25 * (reference(BlendMode::Overlay, a, b) - a);
+
+                assert_eq!(blend(0.0), a);
+                assert_eq!(blend(1.0), OverlayNode::new(ConstNode::new(a), ConstNode::new(b)).value_at(point));
+                assert!((blend(0.25) - expected).abs() < 1e-12);
+
+                assert_eq!(
+                    ScreenNode::new(ConstNode::new(a), ConstNode::new(b)).with_opacity(0.5).value_at(point),
+                    BlendNode::new(ConstNode::new(a), ConstNode::new(b), BlendMode::Screen).with_opacity(0.5).value_at(point)
+                );
+                assert_eq!(
+                    SoftLightNode::new(ConstNode::new(a), ConstNode::new(b)).value_at(point),
+                    BlendNode::new(ConstNode::new(a), ConstNode::new(b), BlendMode::SoftLight).value_at(point)
+                );
+            };
+        };
+    }
+}
@@ -0,0 +1,134 @@
+use std::cell::Cell;
+
+use thread_local::ThreadLocal;
+
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+// A thread's last point (as bits) and the value seen there
+type LastValue<const DIM: usize> = Cell<Option<([u64; DIM], f64)>>;
+
+// Remembers the last value of its source, so a subgraph feeding several combinators is only
+// evaluated once per point. Every thread that samples the node gets its own slot, so lookups
+// never contend; the slots are freed along with the node, and clones start out empty
+pub struct CacheNode<const DIM: usize, Source>
+where Source: NoiseNode<DIM> {
+    source: Source,
+    last_values: ThreadLocal<LastValue<DIM>>
+}
+
+impl<const DIM: usize, Source> CacheNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    pub fn new(source: Source) -> Self {
+        Self { source, last_values: ThreadLocal::new() }
+    }
+}
+
+impl<const DIM: usize, Source> Clone for CacheNode<DIM, Source>
+where Source: NoiseNode<DIM> + Clone {
+    fn clone(&self) -> Self {
+        Self::new(self.source.clone())
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for CacheNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    // Points are compared bit for bit, since sources may tell -0.0 and 0.0 apart
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let key = std::array::from_fn(|dim| point[dim].to_bits());
+        let last_value = self.last_values.get_or_default();
+
+        if let Some((last_key, value)) = last_value.get() {
+            if last_key == key {
+                return value;
+            };
+        };
+
+        let value = self.source.value_at(point);
+
+        last_value.set(Some((key, value)));
+
+        value
+    }
+
+    fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        self.source.fill_grid_section(origin, step, start, extent, output)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        self.source.value_and_gradient(point)
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use std::sync::Barrier;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    use crate::geometry::RealPoint;
+    use crate::noise::{NoiseNode, NoiseNodeExt};
+    use super::CacheNode;
+
+    struct CountingNode {
+        count: AtomicUsize
+    }
+
+    impl NoiseNode<1> for CountingNode {
+        fn value_at(&self, point: RealPoint<1>) -> f64 {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            point[0]
+        }
+    }
+
+    #[test]
+    fn shared_subgraph() {
+        let source = CountingNode { count: AtomicUsize::new(0) };
+        let cached = CacheNode::new(&source);
+
+        let graph = (&cached).multiply(&cached).max(CacheNode::new(&cached));
+
+        assert_eq!(graph.value_at(RealPoint::<1>::new([0.5])), 0.5);
+        assert_eq!(graph.value_at(RealPoint::<1>::new([0.5])), 0.5);
+        assert_eq!(graph.value_at(RealPoint::<1>::new([0.25])), 0.25);
+        assert_eq!(graph.value_at(RealPoint::<1>::new([-0.0])), 0.0);
+        assert_eq!(graph.value_at(RealPoint::<1>::new([0.0])), 0.0);
+
+        assert_eq!(source.count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn cache_lifetime() {
+        let source = CountingNode { count: AtomicUsize::new(0) };
+        let mut cached = CacheNode::new(&source);
+
+        let barrier = Barrier::new(4);
+
+        // Threads running together don't see each other's values, and their slots outlive them
+        // until the node drops (exited threads' slots are handed on to new threads)
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    cached.value_at(RealPoint::<1>::new([0.5]));
+                    cached.value_at(RealPoint::<1>::new([0.5]));
+                    barrier.wait();
+                });
+            };
+        });
+
+        assert_eq!(source.count.load(Ordering::Relaxed), 4);
+        assert_eq!(cached.last_values.iter_mut().count(), 4);
+
+        // Clones keep their own caches
+        let cloned = cached.clone();
+
+        cloned.value_at(RealPoint::<1>::new([0.75]));
+        cloned.value_at(RealPoint::<1>::new([0.75]));
+        cached.value_at(RealPoint::<1>::new([0.75]));
+
+        assert_eq!(source.count.load(Ordering::Relaxed), 6);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+// Alternates between 0.0 and 1.0 across unit cells, so cells sharing a face always differ
+#[derive(Clone)]
+pub struct CheckerboardNode<const DIM: usize>;
+
+impl<const DIM: usize> CheckerboardNode<DIM> {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<const DIM: usize> Default for CheckerboardNode<DIM> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const DIM: usize> NoiseNode<DIM> for CheckerboardNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let parity = point.to_lattice_point().sum().rem_euclid(2);
+
+        parity as f64
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::NoiseNode;
+    use super::CheckerboardNode;
+
+    #[test]
+    fn alternates() {
+        let at = |x: f64, y: f64| RealPoint::<2>::new([x, y]);
+        let checkerboard = CheckerboardNode::<2>::new();
+
+        assert_eq!(checkerboard.value_at(at(0.5, 0.5)), 0.0);
+        assert_eq!(checkerboard.value_at(at(-0.5, 0.5)), 1.0);
+        assert_eq!(checkerboard.value_at(at(-0.5, -0.5)), 0.0);
+    }
+}
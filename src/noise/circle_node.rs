@@ -0,0 +1,34 @@
+use std::f64::consts::TAU;
+
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+// Wraps a line around a circle in 2D, so a 2D source gives a 1D signal that loops seamlessly
+// every `period`. The circle's circumference is the period, keeping the source's scale
+#[derive(Clone)]
+pub struct CircleNode<Source>
+where Source: NoiseNode<2> {
+    source: Source,
+    radius: f64,
+    period: f64
+}
+
+impl<Source> CircleNode<Source>
+where Source: NoiseNode<2> {
+    pub fn new(source: Source, period: f64) -> Self {
+        Self { source, radius: period / TAU, period }
+    }
+
+    pub fn embed(&self, point: RealPoint<1>) -> RealPoint<2> {
+        let angle = point[0] / self.period * TAU;
+
+        RealPoint::<2>::new([self.radius * angle.cos(), self.radius * angle.sin()])
+    }
+}
+
+impl<Source> NoiseNode<1> for CircleNode<Source>
+where Source: NoiseNode<2> {
+    fn value_at(&self, point: RealPoint<1>) -> f64 {
+        self.source.value_at(self.embed(point))
+    }
+}
@@ -0,0 +1,26 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+#[derive(Clone)]
+pub struct ClampNode<const DIM: usize, Source>
+where Source: NoiseNode<DIM> {
+    source: Source,
+    min: f64,
+    max: f64
+}
+
+impl<const DIM: usize, Source> ClampNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    pub fn new(source: Source, min: f64, max: f64) -> Self {
+        assert!(min <= max, "clamps need min <= max");
+
+        Self { source, min, max }
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for ClampNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        self.source.value_at(point).clamp(self.min, self.max)
+    }
+}
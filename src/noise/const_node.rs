@@ -1,6 +1,7 @@
 use crate::geometry::RealPoint;
 use super::NoiseNode;
 
+#[derive(Clone)]
 pub struct ConstNode {
     value: f64
 }
@@ -15,4 +16,8 @@ impl<const DIM: usize> NoiseNode<DIM> for ConstNode {
     fn value_at(&self, _: RealPoint<DIM>) -> f64 {
         self.value
     }
+
+    fn value_and_gradient(&self, _: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        (self.value, RealPoint::<DIM>::origin())
+    }
 }
@@ -0,0 +1,97 @@
+use crate::geometry::RealPoint;
+use super::{NoiseNode, VectorNoiseNode};
+
+// Curl of a scalar potential in 2D, or of a vector potential made of three scalar fields in 3D.
+// The curl of any smooth potential is divergence-free, so the result suits flow fields.
+// `Potential` is a single node in 2D and an array of three in 3D
+#[derive(Clone)]
+pub struct CurlNode<const DIM: usize, Potential> {
+    potential: Potential
+}
+
+impl<Potential> CurlNode<2, Potential>
+where Potential: NoiseNode<2> {
+    pub fn new(potential: Potential) -> Self {
+        Self { potential }
+    }
+}
+
+impl<Potential> CurlNode<3, [Potential; 3]>
+where Potential: NoiseNode<3> {
+    pub fn new(potentials: [Potential; 3]) -> Self {
+        Self { potential: potentials }
+    }
+
+    // Builds each component of the vector potential from its own seed, counting up from `seed`
+    pub fn from_seed(seed: u64, potential: impl Fn(u64) -> Potential) -> Self {
+        Self::new([0, 1, 2].map(|offset| potential(seed.wrapping_add(offset))))
+    }
+}
+
+impl<Potential> VectorNoiseNode<2> for CurlNode<2, Potential>
+where Potential: NoiseNode<2> {
+    fn vector_at(&self, point: RealPoint<2>) -> RealPoint<2> {
+        let (_, gradient) = self.potential.value_and_gradient(point);
+
+        RealPoint::<2>::new([gradient[1], -gradient[0]])
+    }
+}
+
+impl<Potential> VectorNoiseNode<3> for CurlNode<3, [Potential; 3]>
+where Potential: NoiseNode<3> {
+    fn vector_at(&self, point: RealPoint<3>) -> RealPoint<3> {
+        let [x_potential, y_potential, z_potential] = &self.potential;
+
+        let (_, dx) = x_potential.value_and_gradient(point);
+        let (_, dy) = y_potential.value_and_gradient(point);
+        let (_, dz) = z_potential.value_and_gradient(point);
+
+        RealPoint::<3>::new([
+            dz[1] - dy[2],
+            dx[2] - dz[0],
+            dy[0] - dx[1]
+        ])
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::{PerlinNode, SimplexNode, VectorNoiseNode};
+    use super::CurlNode;
+
+    const STEP: f64 = 1e-5;
+
+    fn divergence<const DIM: usize>(node: &impl VectorNoiseNode<DIM>, point: RealPoint<DIM>) -> f64 {
+        let mut divergence = 0.0;
+
+        for dim in 0..DIM {
+            let mut offset = RealPoint::<DIM>::origin();
+
+            offset[dim] = STEP;
+
+            divergence += (node.vector_at(point + offset)[dim] - node.vector_at(point - offset)[dim]) / (2.0 * STEP);
+        };
+
+        divergence
+    }
+
+    #[test]
+    fn divergence_free() {
+        let curl_2d = CurlNode::<2, _>::new(PerlinNode::<2>::new(1));
+        let curl_3d = CurlNode::<3, _>::from_seed(1, SimplexNode::<3>::new);
+
+        for idx in 0..100 {
+            let t = idx as f64;
+
+            let point_2d = RealPoint::<2>::new([t * 0.37 - 10.0, t * 0.61 - 20.0]);
+            let point_3d = RealPoint::<3>::new([t * 0.37 - 10.0, t * 0.61 - 20.0, t * 0.13]);
+
+            assert!(divergence(&curl_2d, point_2d).abs() < 1e-4);
+            assert!(divergence(&curl_3d, point_3d).abs() < 1e-4);
+        };
+    }
+}
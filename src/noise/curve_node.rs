@@ -0,0 +1,200 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CurveInterpolation {
+    Linear,
+
+    // Catmull-Rom style Hermite splines, which may overshoot between control points
+    Cubic,
+
+    // Fritsch-Carlson Hermite splines, which never overshoot, so a monotone set of control points
+    // gives a monotone curve
+    MonotoneCubic
+}
+
+// Maps source values through a transfer curve through `(input, output)` control points. Values
+// outside the control points take the output of the nearest end
+#[derive(Clone)]
+pub struct CurveNode<const DIM: usize, Source>
+where Source: NoiseNode<DIM> {
+    source: Source,
+    control_points: Vec<(f64, f64)>,
+    tangents: Vec<f64>,
+    interpolation: CurveInterpolation
+}
+
+impl<const DIM: usize, Source> CurveNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    pub fn new(source: Source, mut control_points: Vec<(f64, f64)>, interpolation: CurveInterpolation) -> Self {
+        assert!(control_points.len() >= 2, "curves need at least two control points");
+
+        control_points.sort_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs));
+
+        assert!(control_points.windows(2).all(|pair| pair[0].0 < pair[1].0), "control points need distinct inputs");
+
+        let tangents = match interpolation {
+            CurveInterpolation::Linear => Vec::with_capacity(0),
+            CurveInterpolation::Cubic => cubic_tangents(&control_points),
+            CurveInterpolation::MonotoneCubic => monotone_tangents(&control_points)
+        };
+
+        Self { source, control_points, tangents, interpolation }
+    }
+
+    // The curve's output and slope at `value`
+    pub fn map(&self, value: f64) -> (f64, f64) {
+        let (first, last) = (self.control_points[0], self.control_points[self.control_points.len() - 1]);
+
+        if value <= first.0 {
+            return (first.1, 0.0);
+        };
+
+        if value >= last.0 {
+            return (last.1, 0.0);
+        };
+
+        // Index of the control point ending the segment that holds `value`
+        let end = self.control_points.partition_point(|&(input, _)| input <= value);
+
+        let (x0, y0) = self.control_points[end - 1];
+        let (x1, y1) = self.control_points[end];
+
+        let width = x1 - x0;
+        let t = (value - x0) / width;
+
+        match self.interpolation {
+            CurveInterpolation::Linear => (y0 + (y1 - y0) * t, (y1 - y0) / width),
+            CurveInterpolation::Cubic | CurveInterpolation::MonotoneCubic => {
+                hermite(t, width, y0, y1, self.tangents[end - 1], self.tangents[end])
+            }
+        }
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for CurveNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        self.map(self.source.value_at(point)).0
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (value, gradient) = self.source.value_and_gradient(point);
+        let (curve_value, slope) = self.map(value);
+
+        (curve_value, gradient * slope)
+    }
+}
+
+// Value and slope of the cubic Hermite segment of `width` between `y0` and `y1`, at `t` in
+// [0.0, 1.0] along it
+fn hermite(t: f64, width: f64, y0: f64, y1: f64, m0: f64, m1: f64) -> (f64, f64) {
+    let (t2, t3) = (t * t, t * t * t);
+
+    let value = (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+        + (t3 - 2.0 * t2 + t) * width * m0
+        + (-2.0 * t3 + 3.0 * t2) * y1
+        + (t3 - t2) * width * m1;
+
+    let slope = (6.0 * t2 - 6.0 * t) * (y0 - y1) / width
+        + (3.0 * t2 - 4.0 * t + 1.0) * m0
+        + (3.0 * t2 - 2.0 * t) * m1;
+
+    (value, slope)
+}
+
+fn secants(control_points: &[(f64, f64)]) -> Vec<f64> {
+    control_points.windows(2).map(|pair| (pair[1].1 - pair[0].1) / (pair[1].0 - pair[0].0)).collect()
+}
+
+// Inner tangents run parallel to the chord between their neighbours, end tangents follow the end
+// segments
+fn cubic_tangents(control_points: &[(f64, f64)]) -> Vec<f64> {
+    let secants = secants(control_points);
+    let last = control_points.len() - 1;
+
+    (0..=last).map(|idx| {
+        if idx == 0 {
+            secants[0]
+        } else if idx == last {
+            secants[last - 1]
+        } else {
+            let (x0, y0) = control_points[idx - 1];
+            let (x1, y1) = control_points[idx + 1];
+
+            (y1 - y0) / (x1 - x0)
+        }
+    }).collect()
+}
+
+fn monotone_tangents(control_points: &[(f64, f64)]) -> Vec<f64> {
+    let secants = secants(control_points);
+    let last = control_points.len() - 1;
+
+    let mut tangents: Vec<f64> = (0..=last).map(|idx| {
+        if idx == 0 {
+            secants[0]
+        } else if idx == last {
+            secants[last - 1]
+        } else if secants[idx - 1] * secants[idx] <= 0.0 {
+            // Local extrema stay flat
+            0.0
+        } else {
+            (secants[idx - 1] + secants[idx]) / 2.0
+        }
+    }).collect();
+
+    // Tangents more than three times the secant on either side would overshoot, so they are
+    // scaled back onto that circle
+    for (idx, &secant) in secants.iter().enumerate() {
+        if secant == 0.0 {
+            tangents[idx] = 0.0;
+            tangents[idx + 1] = 0.0;
+            continue;
+        };
+
+        let alpha = tangents[idx] / secant;
+        let beta = tangents[idx + 1] / secant;
+        let magnitude = alpha.hypot(beta);
+
+        if magnitude > 3.0 {
+            let scale = 3.0 / magnitude;
+
+            tangents[idx] = scale * alpha * secant;
+            tangents[idx + 1] = scale * beta * secant;
+        };
+    };
+
+    tangents
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::noise::ConstNode;
+    use super::{CurveInterpolation, CurveNode};
+
+    #[test]
+    fn interpolations() {
+        let control_points = vec![(0.0, 0.0), (0.3, 0.1), (0.4, 0.8), (1.0, 1.0)];
+
+        for interpolation in [CurveInterpolation::Linear, CurveInterpolation::Cubic, CurveInterpolation::MonotoneCubic] {
+            let curve = CurveNode::<1, _>::new(ConstNode::new(0.0), control_points.clone(), interpolation);
+
+            for &(input, output) in control_points.iter() {
+                assert!((curve.map(input).0 - output).abs() < 1e-12);
+            };
+        };
+
+        let cubic = CurveNode::<1, _>::new(ConstNode::new(0.0), control_points.clone(), CurveInterpolation::Cubic);
+        let monotone = CurveNode::<1, _>::new(ConstNode::new(0.0), control_points, CurveInterpolation::MonotoneCubic);
+
+        let samples: Vec<f64> = (0..=1000).map(|idx| (idx as f64) / 1000.0).collect();
+
+        // The steep middle segment makes the plain cubic dip below its first control point
+        assert!(samples.iter().any(|&x| cubic.map(x).0 < 0.0));
+        assert!(samples.windows(2).all(|pair| monotone.map(pair[0]).0 <= monotone.map(pair[1]).0));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::geometry::{DistanceMetric, RealPoint};
+use super::{HypersphereNode, NoiseNode, PatternProfile};
+
+// Concentric shells around `axis`, like `HypersphereNode` with that axis left out
+#[derive(Clone)]
+pub struct CylindersNode<const DIM: usize, Metric>
+where Metric: DistanceMetric {
+    shells: HypersphereNode<DIM, Metric>,
+    axis: usize
+}
+
+impl<const DIM: usize, Metric> CylindersNode<DIM, Metric>
+where Metric: DistanceMetric {
+    pub fn new(frequency: f64, axis: usize) -> Self {
+        assert!(axis < DIM, "cylinder axes must lie within {} dimensions", DIM);
+
+        Self { shells: HypersphereNode::new(frequency), axis }
+    }
+
+    pub fn with_profile(mut self, profile: PatternProfile) -> Self {
+        self.shells = self.shells.with_profile(profile);
+        self
+    }
+}
+
+impl<const DIM: usize, Metric> NoiseNode<DIM> for CylindersNode<DIM, Metric>
+where Metric: DistanceMetric {
+    fn value_at(&self, mut point: RealPoint<DIM>) -> f64 {
+        point[self.axis] = 0.0;
+
+        self.shells.value_at(point)
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{EuclideanMetric, RealPoint};
+    use crate::noise::{NoiseNode, PatternProfile};
+    use super::CylindersNode;
+
+    #[test]
+    fn linear_profile() {
+        let at = |x: f64, y: f64| RealPoint::<2>::new([x, y]);
+        let cylinders = CylindersNode::<2, EuclideanMetric>::new(1.0, 1).with_profile(PatternProfile::Linear);
+
+        assert_eq!(cylinders.value_at(at(2.0, 7.3)), 1.0);
+        assert_eq!(cylinders.value_at(at(2.25, -1.0)), 0.5);
+    }
+}
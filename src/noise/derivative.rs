@@ -0,0 +1,115 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+// Distance sampled on either side of a point by `central_difference`, close to the cube root of
+// machine epsilon, where truncation and rounding errors balance out for unit-scale features
+pub const DIFFERENCE_STEP: f64 = 1e-5;
+
+// The default `NoiseNode::value_and_gradient`, approximating every partial derivative from two
+// extra samples along its axis
+pub fn central_difference<const DIM: usize, Node>(node: &Node, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>)
+where Node: NoiseNode<DIM> + ?Sized {
+    let mut gradient = RealPoint::<DIM>::origin();
+
+    for dim in 0..DIM {
+        let mut offset = RealPoint::<DIM>::origin();
+
+        offset[dim] = DIFFERENCE_STEP;
+
+        let lhs = node.value_at(point - offset);
+        let rhs = node.value_at(point + offset);
+
+        gradient[dim] = (rhs - lhs) / (2.0 * DIFFERENCE_STEP);
+    };
+
+    (node.value_at(point), gradient)
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{AffineMap, LinearMap, RealPoint};
+    use crate::noise::{
+        CurveInterpolation, ExtrudeNode, FractalKind, NoiseNode, NoiseNodeExt, PerlinNode, SimplexNode, SliceNode,
+        ValueInterpolation, ValueNode
+    };
+    use crate::random::StatefulRand;
+    use super::central_difference;
+
+    fn assert_matches_central_difference<const DIM: usize>(node: &impl NoiseNode<DIM>) {
+        let mut rng = StatefulRand::from_seed(1);
+
+        for _ in 0..200 {
+            let mut point = RealPoint::<DIM>::origin();
+
+            for coord in point.iter_mut() {
+                *coord = rng.get_f64() * 20.0 - 10.0;
+            };
+
+            let (value, gradient) = node.value_and_gradient(point);
+            let (expected_value, expected_gradient) = central_difference(node, point);
+
+            assert!((value - expected_value).abs() < 1e-12, "{} != {} at {}", value, expected_value, point);
+
+            for (&actual, &expected) in gradient.iter().zip(expected_gradient.iter()) {
+                assert!((actual - expected).abs() < 1e-4 * expected.abs().max(1.0), "{} != {} at {}", gradient, expected_gradient, point);
+            };
+        };
+    }
+
+    #[test]
+    fn perlin_gradients() {
+        assert_matches_central_difference(&PerlinNode::<1>::new(1));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1));
+        assert_matches_central_difference(&PerlinNode::<3>::new(1));
+        assert_matches_central_difference(&PerlinNode::<4>::new(1));
+    }
+
+    #[test]
+    fn simplex_gradients() {
+        assert_matches_central_difference(&SimplexNode::<1>::new(1));
+        assert_matches_central_difference(&SimplexNode::<2>::new(1));
+        assert_matches_central_difference(&SimplexNode::<3>::new(1));
+        assert_matches_central_difference(&SimplexNode::<4>::new(1));
+        assert_matches_central_difference(&SimplexNode::<5>::new(1));
+    }
+
+    #[test]
+    fn value_gradients() {
+        assert_matches_central_difference(&ValueNode::<2>::new(1, ValueInterpolation::Linear));
+        assert_matches_central_difference(&ValueNode::<2>::new(1, ValueInterpolation::Cosine));
+        assert_matches_central_difference(&ValueNode::<3>::new(1, ValueInterpolation::Quintic));
+        assert_matches_central_difference(&ValueNode::<2>::new(1, ValueInterpolation::Cubic));
+    }
+
+    #[test]
+    fn combinator_gradients() {
+        let linear_map = LinearMap::<2>::new([
+            [0.8, -0.6],
+            [0.3,  1.2]
+        ]);
+
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).fbm(4, 0.5, 2.0));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).fbm(4, 0.5, 2.0).with_kind(FractalKind::Billow).with_octave_offsets(3));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).fbm(4, 0.5, 2.0).with_octave_rotation(linear_map.clone()).with_fractional_octaves(3.5));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).multiply(SimplexNode::<2>::new(2)));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).multiply(SimplexNode::<2>::new(2)).with_opacity(0.3));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).lerp(SimplexNode::<2>::new(2), ValueNode::<2>::new(3, ValueInterpolation::Quintic)));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).transform(AffineMap::new(linear_map, RealPoint::<2>::new([0.5, -3.0]))));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).sigmoid(2.0));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).select(SimplexNode::<2>::new(2), ValueNode::<2>::new(3, ValueInterpolation::Quintic), 0.3, 0.7).with_falloff(0.1));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).curve(vec![(0.0, 0.0), (0.4, 0.7), (1.0, 1.0)], CurveInterpolation::MonotoneCubic));
+        assert_matches_central_difference(&PerlinNode::<2>::new(1).terrace(4).with_inversion(true));
+
+        assert_matches_central_difference(&SliceNode::new(PerlinNode::<3>::new(1), RealPoint::<3>::new([0.0, 0.0, 0.5]), [
+            RealPoint::<3>::new([0.6, 0.0, 0.8]),
+            RealPoint::<3>::new([0.0, 1.0, 0.0])
+        ]));
+        assert_matches_central_difference(&ExtrudeNode::<3, 2, _>::from_projection(PerlinNode::<2>::new(1), [
+            RealPoint::<3>::new([1.0, 0.5, 0.0]),
+            RealPoint::<3>::new([0.0, 0.0, 1.0])
+        ]));
+    }
+}
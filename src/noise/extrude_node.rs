@@ -0,0 +1,54 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+// Lifts a lower-dimensional source into DIM dimensions, taking each source coordinate as the dot
+// product of the point with one row of `projection`. Picking axes ignores the rest, so the source
+// is extruded unchanged along them
+#[derive(Clone)]
+pub struct ExtrudeNode<const DIM: usize, const SOURCE_DIM: usize, Source>
+where Source: NoiseNode<SOURCE_DIM> {
+    source: Source,
+    projection: [RealPoint<DIM>; SOURCE_DIM]
+}
+
+impl<const DIM: usize, const SOURCE_DIM: usize, Source> ExtrudeNode<DIM, SOURCE_DIM, Source>
+where Source: NoiseNode<SOURCE_DIM> {
+    // Source axis N reads input axis `axes[N]`
+    pub fn new(source: Source, axes: [usize; SOURCE_DIM]) -> Self {
+        assert!(axes.iter().all(|&axis| axis < DIM), "extruded axes must lie within {} dimensions", DIM);
+
+        let projection = axes.map(|axis| {
+            let mut row = RealPoint::<DIM>::origin();
+
+            row[axis] = 1.0;
+            row
+        });
+
+        Self::from_projection(source, projection)
+    }
+
+    pub fn from_projection(source: Source, projection: [RealPoint<DIM>; SOURCE_DIM]) -> Self {
+        Self { source, projection }
+    }
+
+    pub fn project(&self, point: RealPoint<DIM>) -> RealPoint<SOURCE_DIM> {
+        RealPoint::<SOURCE_DIM>::new(self.projection.map(|row| row.dot_product(point)))
+    }
+}
+
+impl<const DIM: usize, const SOURCE_DIM: usize, Source> NoiseNode<DIM> for ExtrudeNode<DIM, SOURCE_DIM, Source>
+where Source: NoiseNode<SOURCE_DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        self.source.value_at(self.project(point))
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (value, source_gradient) = self.source.value_and_gradient(self.project(point));
+
+        let gradient = self.projection.iter().zip(source_gradient.iter()).fold(RealPoint::<DIM>::origin(), |gradient, (&row, &slope)| {
+            gradient + row * slope
+        });
+
+        (value, gradient)
+    }
+}
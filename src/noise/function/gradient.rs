@@ -108,6 +108,45 @@ pub const GRADIENTS_3D: [(f64, f64, f64); 64] = [
     (-0.7667744834466065,  0.3253048720565017,  0.5533838014899721)
 ];
 
+// Edge midpoints of the tesseract, scaled to unit length
+pub const GRADIENTS_4D: [(f64, f64, f64, f64); 32] = [
+    ( 0.0000000000000000,  0.5773502691896258,  0.5773502691896258,  0.5773502691896258),
+    ( 0.0000000000000000,  0.5773502691896258,  0.5773502691896258, -0.5773502691896258),
+    ( 0.0000000000000000,  0.5773502691896258, -0.5773502691896258,  0.5773502691896258),
+    ( 0.0000000000000000,  0.5773502691896258, -0.5773502691896258, -0.5773502691896258),
+    ( 0.0000000000000000, -0.5773502691896258,  0.5773502691896258,  0.5773502691896258),
+    ( 0.0000000000000000, -0.5773502691896258,  0.5773502691896258, -0.5773502691896258),
+    ( 0.0000000000000000, -0.5773502691896258, -0.5773502691896258,  0.5773502691896258),
+    ( 0.0000000000000000, -0.5773502691896258, -0.5773502691896258, -0.5773502691896258),
+
+    ( 0.5773502691896258,  0.0000000000000000,  0.5773502691896258,  0.5773502691896258),
+    ( 0.5773502691896258,  0.0000000000000000,  0.5773502691896258, -0.5773502691896258),
+    ( 0.5773502691896258,  0.0000000000000000, -0.5773502691896258,  0.5773502691896258),
+    ( 0.5773502691896258,  0.0000000000000000, -0.5773502691896258, -0.5773502691896258),
+    (-0.5773502691896258,  0.0000000000000000,  0.5773502691896258,  0.5773502691896258),
+    (-0.5773502691896258,  0.0000000000000000,  0.5773502691896258, -0.5773502691896258),
+    (-0.5773502691896258,  0.0000000000000000, -0.5773502691896258,  0.5773502691896258),
+    (-0.5773502691896258,  0.0000000000000000, -0.5773502691896258, -0.5773502691896258),
+
+    ( 0.5773502691896258,  0.5773502691896258,  0.0000000000000000,  0.5773502691896258),
+    ( 0.5773502691896258,  0.5773502691896258,  0.0000000000000000, -0.5773502691896258),
+    ( 0.5773502691896258, -0.5773502691896258,  0.0000000000000000,  0.5773502691896258),
+    ( 0.5773502691896258, -0.5773502691896258,  0.0000000000000000, -0.5773502691896258),
+    (-0.5773502691896258,  0.5773502691896258,  0.0000000000000000,  0.5773502691896258),
+    (-0.5773502691896258,  0.5773502691896258,  0.0000000000000000, -0.5773502691896258),
+    (-0.5773502691896258, -0.5773502691896258,  0.0000000000000000,  0.5773502691896258),
+    (-0.5773502691896258, -0.5773502691896258,  0.0000000000000000, -0.5773502691896258),
+
+    ( 0.5773502691896258,  0.5773502691896258,  0.5773502691896258,  0.0000000000000000),
+    ( 0.5773502691896258,  0.5773502691896258, -0.5773502691896258,  0.0000000000000000),
+    ( 0.5773502691896258, -0.5773502691896258,  0.5773502691896258,  0.0000000000000000),
+    ( 0.5773502691896258, -0.5773502691896258, -0.5773502691896258,  0.0000000000000000),
+    (-0.5773502691896258,  0.5773502691896258,  0.5773502691896258,  0.0000000000000000),
+    (-0.5773502691896258,  0.5773502691896258, -0.5773502691896258,  0.0000000000000000),
+    (-0.5773502691896258, -0.5773502691896258,  0.5773502691896258,  0.0000000000000000),
+    (-0.5773502691896258, -0.5773502691896258, -0.5773502691896258,  0.0000000000000000)
+];
+
 pub fn gen_gradients<const DIM: usize>(seed: u64, num_gradients: usize) -> Vec<RealPoint<DIM>> {
     let mut gradients: Vec<RealPoint<DIM>> = Vec::with_capacity(num_gradients);
     let mut idx: usize = 0;
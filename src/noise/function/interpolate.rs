@@ -0,0 +1,56 @@
+use crate::geometry::RealPoint;
+use crate::utils;
+
+// Collapses the values at the 2^N vertices of a hypercube one axis at a time, in the order of
+// `VertexNeighborhood`. `biases` are the interpolation weights along each axis, and `values` is
+// used as scratch space
+pub fn lerp_vertices<const DIM: usize>(values: &mut [f64], biases: RealPoint<DIM>) -> f64 {
+    let mut len = values.len();
+
+    for dim in 0..DIM {
+        let bias = biases[dim];
+
+        len /= 2;
+
+        for idx in 0..len {
+            values[idx] = utils::lerp(bias, values[idx * 2], values[idx * 2 + 1]);
+        };
+    };
+
+    values[0]
+}
+
+// Collapses the values at the 2^N vertices of a hypercube one axis at a time, in the order of
+// `VertexNeighborhood`, while carrying the gradient of every partial result along. `biases` are
+// the interpolation weights along each axis and `slopes` their derivatives. Both slices are used
+// as scratch space
+pub fn lerp_vertices_with_gradient<const DIM: usize>(values: &mut [f64], gradients: &mut [RealPoint<DIM>], biases: RealPoint<DIM>, slopes: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+    let mut len = values.len();
+
+    for dim in 0..DIM {
+        let bias = biases[dim];
+
+        len /= 2;
+
+        for idx in 0..len {
+            let lhs = values[idx * 2];
+            let rhs = values[idx * 2 + 1];
+
+            let lhs_gradient = gradients[idx * 2];
+            let rhs_gradient = gradients[idx * 2 + 1];
+
+            let mut gradient = RealPoint::<DIM>::origin();
+
+            for axis in 0..DIM {
+                gradient[axis] = utils::lerp(bias, lhs_gradient[axis], rhs_gradient[axis]);
+            };
+
+            gradient[dim] += slopes[dim] * (rhs - lhs);
+
+            values[idx] = utils::lerp(bias, lhs, rhs);
+            gradients[idx] = gradient;
+        };
+    };
+
+    (values[0], gradients[0])
+}
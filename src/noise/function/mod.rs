@@ -1,5 +1,9 @@
 mod gradient;
+mod interpolate;
 mod perlin;
+mod simplex;
 
 pub use gradient::*;
+pub use interpolate::*;
 pub use perlin::*;
+pub use simplex::*;
@@ -3,27 +3,57 @@ use crate::geometry::RealPoint;
 use crate::random::StatelessRand;
 use crate::utils;
 use super::gradient::*;
+use super::interpolate::*;
 
 const PERLIN_BIAS_2D: f64 = 2.0_f64 / SQRT_2;
 const PERLIN_BIAS_3D: f64 = 1.1547005383792517;
 
+// Lattice coordinates hashed for the lower and upper bounds of a cell along one axis. Periodic
+// noise wraps both into [0, period), so vertices a whole period apart share a gradient
+fn cell_bounds(a0: f64, period: Option<f64>) -> (f64, f64) {
+    match period {
+        Some(period) => (wrap_lattice(a0, period), wrap_lattice(a0 + 1.0, period)),
+        None => (a0, a0 + 1.0)
+    }
+}
+
+// Exact multiples of the period wrap to -0.0 when negative, which would hash differently from 0.0
+pub fn wrap_lattice(coordinate: f64, period: f64) -> f64 {
+    coordinate.rem_euclid(period) + 0.0
+}
+
  //-------------------------------------------------------------------------------------------------
 // 1D Perlin noise
 
-pub fn perlin_1d(rng: StatelessRand, point: RealPoint<1>) -> f64 {
+pub fn perlin_1d(rng: StatelessRand, period: Option<RealPoint<1>>, point: RealPoint<1>) -> f64 {
     let px = point[0];
 
     let ax0 = px.floor();
-    let ax1 = ax0 + 1.0;
+    let (hx0, hx1) = cell_bounds(ax0, period.map(|period| period[0]));
 
-    let v0 = vertex_1d(rng, ax0);
-    let v1 = vertex_1d(rng, ax1);
+    let v0 = vertex_1d(rng, hx0);
+    let v1 = vertex_1d(rng, hx1);
 
     let vx = utils::cerp(px - ax0, v0, v1);
 
     utils::smoothstep(vx)
 }
 
+pub fn perlin_1d_with_gradient(rng: StatelessRand, period: Option<RealPoint<1>>, point: RealPoint<1>) -> (f64, RealPoint<1>) {
+    let px = point[0];
+
+    let ax0 = px.floor();
+    let (hx0, hx1) = cell_bounds(ax0, period.map(|period| period[0]));
+
+    let v0 = vertex_1d(rng, hx0);
+    let v1 = vertex_1d(rng, hx1);
+
+    let vx = utils::cerp(px - ax0, v0, v1);
+    let dx = utils::cerp_derivative(px - ax0, v0, v1);
+
+    (utils::smoothstep(vx), RealPoint::<1>::new([utils::smoothstep_derivative(vx) * dx]))
+}
+
 fn vertex_1d(rng: StatelessRand, x: f64) -> f64 {
     let hash = rng.hash_1u64(x.to_bits());
 
@@ -34,26 +64,41 @@ fn vertex_1d(rng: StatelessRand, x: f64) -> f64 {
  //-------------------------------------------------------------------------------------------------
 // 2D Perlin noise
 
-pub fn perlin_2d(rng: StatelessRand, point: RealPoint<2>) -> f64 {
+pub fn perlin_2d(rng: StatelessRand, period: Option<RealPoint<2>>, point: RealPoint<2>) -> f64 {
     let px = point[0];
     let py = point[1];
 
     let ax0 = px.floor();
     let ay0 = py.floor();
 
-    let ax1 = ax0 + 1.0;
-    let ay1 = ay0 + 1.0;
+    let gradients = cell_gradients_2d(rng, period, ax0, ay0);
 
-    let nx0 = px - ax0;
-    let ny0 = py - ay0;
+    perlin_2d_in_cell(&gradients, px - ax0, py - ay0)
+}
+
+// Gradients at the vertices of the cell whose lowest vertex is (ax0, ay0), in the order of
+// `VertexNeighborhood`
+pub fn cell_gradients_2d(rng: StatelessRand, period: Option<RealPoint<2>>, ax0: f64, ay0: f64) -> [(f64, f64); 4] {
+    let (ax0, ax1) = cell_bounds(ax0, period.map(|period| period[0]));
+    let (ay0, ay1) = cell_bounds(ay0, period.map(|period| period[1]));
+
+    [
+        gradient_2d(rng, ax0, ay0),
+        gradient_2d(rng, ax1, ay0),
+        gradient_2d(rng, ax0, ay1),
+        gradient_2d(rng, ax1, ay1)
+    ]
+}
 
+// Offsets are measured from the cell's lowest vertex, and lie in [0.0, 1.0)
+pub fn perlin_2d_in_cell(gradients: &[(f64, f64); 4], nx0: f64, ny0: f64) -> f64 {
     let nx1 = nx0 - 1.0;
     let ny1 = ny0 - 1.0;
 
-    let v00 = vertex_2d(rng, ax0, ay0, nx0, ny0);
-    let v10 = vertex_2d(rng, ax1, ay0, nx1, ny0);
-    let v01 = vertex_2d(rng, ax0, ay1, nx0, ny1);
-    let v11 = vertex_2d(rng, ax1, ay1, nx1, ny1);
+    let v00 = vertex_2d(gradients[0], nx0, ny0);
+    let v10 = vertex_2d(gradients[1], nx1, ny0);
+    let v01 = vertex_2d(gradients[2], nx0, ny1);
+    let v11 = vertex_2d(gradients[3], nx1, ny1);
 
     let sx = utils::smoothstep(nx0);
     let sy = utils::smoothstep(ny0);
@@ -66,17 +111,52 @@ pub fn perlin_2d(rng: StatelessRand, point: RealPoint<2>) -> f64 {
     utils::smoothstep(utils::neg_unit_to_unit(vxy * PERLIN_BIAS_2D))
 }
 
-fn vertex_2d(rng: StatelessRand, ax: f64, ay: f64, nx: f64, ny: f64) -> f64 {
+pub fn perlin_2d_with_gradient(rng: StatelessRand, period: Option<RealPoint<2>>, point: RealPoint<2>) -> (f64, RealPoint<2>) {
+    let ax0 = point[0].floor();
+    let ay0 = point[1].floor();
+
+    let nx0 = point[0] - ax0;
+    let ny0 = point[1] - ay0;
+    let nx1 = nx0 - 1.0;
+    let ny1 = ny0 - 1.0;
+
+    let gradients = cell_gradients_2d(rng, period, ax0, ay0);
+
+    let mut values = [
+        vertex_2d(gradients[0], nx0, ny0),
+        vertex_2d(gradients[1], nx1, ny0),
+        vertex_2d(gradients[2], nx0, ny1),
+        vertex_2d(gradients[3], nx1, ny1)
+    ];
+
+    // Each vertex's dot product changes with the point at the rate of its gradient
+    let mut vertex_gradients = gradients.map(|(gx, gy)| RealPoint::<2>::new([gx, gy]));
+
+    let biases = RealPoint::<2>::new([nx0, ny0].map(utils::smoothstep));
+    let slopes = RealPoint::<2>::new([nx0, ny0].map(utils::smoothstep_derivative));
+
+    let (vxy, gradient) = lerp_vertices_with_gradient(&mut values, &mut vertex_gradients, biases, slopes);
+
+    let unit_value = utils::neg_unit_to_unit(vxy * PERLIN_BIAS_2D);
+    let slope = utils::smoothstep_derivative(unit_value) * 0.5 * PERLIN_BIAS_2D;
+
+    (utils::smoothstep(unit_value), gradient * slope)
+}
+
+fn gradient_2d(rng: StatelessRand, ax: f64, ay: f64) -> (f64, f64) {
     let hash = rng.hash_2u64(ax.to_bits(), ay.to_bits()) as usize;
-    let (gx, gy) = GRADIENTS_2D[hash & 31];
 
+    GRADIENTS_2D[hash & 31]
+}
+
+fn vertex_2d((gx, gy): (f64, f64), nx: f64, ny: f64) -> f64 {
     gx * nx + gy * ny
 }
 
  //-------------------------------------------------------------------------------------------------
 // 3D Perlin noise
 
-pub fn perlin_3d(rng: StatelessRand, point: RealPoint<3>) -> f64 {
+pub fn perlin_3d(rng: StatelessRand, period: Option<RealPoint<3>>, point: RealPoint<3>) -> f64 {
     let px = point[0];
     let py = point[1];
     let pz = point[2];
@@ -85,26 +165,44 @@ pub fn perlin_3d(rng: StatelessRand, point: RealPoint<3>) -> f64 {
     let ay0 = py.floor();
     let az0 = pz.floor();
 
-    let ax1 = ax0 + 1.0;
-    let ay1 = ay0 + 1.0;
-    let az1 = az0 + 1.0;
+    let gradients = cell_gradients_3d(rng, period, ax0, ay0, az0);
+
+    perlin_3d_in_cell(&gradients, px - ax0, py - ay0, pz - az0)
+}
 
-    let nx0 = px - ax0;
-    let ny0 = py - ay0;
-    let nz0 = pz - az0;
+// Gradients at the vertices of the cell whose lowest vertex is (ax0, ay0, az0), in the order of
+// `VertexNeighborhood`
+pub fn cell_gradients_3d(rng: StatelessRand, period: Option<RealPoint<3>>, ax0: f64, ay0: f64, az0: f64) -> [(f64, f64, f64); 8] {
+    let (ax0, ax1) = cell_bounds(ax0, period.map(|period| period[0]));
+    let (ay0, ay1) = cell_bounds(ay0, period.map(|period| period[1]));
+    let (az0, az1) = cell_bounds(az0, period.map(|period| period[2]));
+
+    [
+        gradient_3d(rng, ax0, ay0, az0),
+        gradient_3d(rng, ax1, ay0, az0),
+        gradient_3d(rng, ax0, ay1, az0),
+        gradient_3d(rng, ax1, ay1, az0),
+        gradient_3d(rng, ax0, ay0, az1),
+        gradient_3d(rng, ax1, ay0, az1),
+        gradient_3d(rng, ax0, ay1, az1),
+        gradient_3d(rng, ax1, ay1, az1)
+    ]
+}
 
+// Offsets are measured from the cell's lowest vertex, and lie in [0.0, 1.0)
+pub fn perlin_3d_in_cell(gradients: &[(f64, f64, f64); 8], nx0: f64, ny0: f64, nz0: f64) -> f64 {
     let nx1 = nx0 - 1.0;
     let ny1 = ny0 - 1.0;
     let nz1 = nz0 - 1.0;
 
-    let v000 = vertex_3d(rng, ax0, ay0, az0, nx0, ny0, nz0);
-    let v100 = vertex_3d(rng, ax1, ay0, az0, nx1, ny0, nz0);
-    let v010 = vertex_3d(rng, ax0, ay1, az0, nx0, ny1, nz0);
-    let v110 = vertex_3d(rng, ax1, ay1, az0, nx1, ny1, nz0);
-    let v001 = vertex_3d(rng, ax0, ay0, az1, nx0, ny0, nz1);
-    let v101 = vertex_3d(rng, ax1, ay0, az1, nx1, ny0, nz1);
-    let v011 = vertex_3d(rng, ax0, ay1, az1, nx0, ny1, nz1);
-    let v111 = vertex_3d(rng, ax1, ay1, az1, nx1, ny1, nz1);
+    let v000 = vertex_3d(gradients[0], nx0, ny0, nz0);
+    let v100 = vertex_3d(gradients[1], nx1, ny0, nz0);
+    let v010 = vertex_3d(gradients[2], nx0, ny1, nz0);
+    let v110 = vertex_3d(gradients[3], nx1, ny1, nz0);
+    let v001 = vertex_3d(gradients[4], nx0, ny0, nz1);
+    let v101 = vertex_3d(gradients[5], nx1, ny0, nz1);
+    let v011 = vertex_3d(gradients[6], nx0, ny1, nz1);
+    let v111 = vertex_3d(gradients[7], nx1, ny1, nz1);
 
     let sx = utils::smoothstep(nx0);
     let sy = utils::smoothstep(ny0);
@@ -123,9 +221,54 @@ pub fn perlin_3d(rng: StatelessRand, point: RealPoint<3>) -> f64 {
     utils::sigmoid(-1.5, utils::neg_unit_to_unit(utils::neg_smoothstep(vxyz * PERLIN_BIAS_3D)))
 }
 
-fn vertex_3d(rng: StatelessRand, ax: f64, ay: f64, az: f64, nx: f64, ny: f64, nz: f64) -> f64 {
+pub fn perlin_3d_with_gradient(rng: StatelessRand, period: Option<RealPoint<3>>, point: RealPoint<3>) -> (f64, RealPoint<3>) {
+    let ax0 = point[0].floor();
+    let ay0 = point[1].floor();
+    let az0 = point[2].floor();
+
+    let nx0 = point[0] - ax0;
+    let ny0 = point[1] - ay0;
+    let nz0 = point[2] - az0;
+    let nx1 = nx0 - 1.0;
+    let ny1 = ny0 - 1.0;
+    let nz1 = nz0 - 1.0;
+
+    let gradients = cell_gradients_3d(rng, period, ax0, ay0, az0);
+
+    let mut values = [
+        vertex_3d(gradients[0], nx0, ny0, nz0),
+        vertex_3d(gradients[1], nx1, ny0, nz0),
+        vertex_3d(gradients[2], nx0, ny1, nz0),
+        vertex_3d(gradients[3], nx1, ny1, nz0),
+        vertex_3d(gradients[4], nx0, ny0, nz1),
+        vertex_3d(gradients[5], nx1, ny0, nz1),
+        vertex_3d(gradients[6], nx0, ny1, nz1),
+        vertex_3d(gradients[7], nx1, ny1, nz1)
+    ];
+
+    let mut vertex_gradients = gradients.map(|(gx, gy, gz)| RealPoint::<3>::new([gx, gy, gz]));
+
+    let biases = RealPoint::<3>::new([nx0, ny0, nz0].map(utils::smoothstep));
+    let slopes = RealPoint::<3>::new([nx0, ny0, nz0].map(utils::smoothstep_derivative));
+
+    let (vxyz, gradient) = lerp_vertices_with_gradient(&mut values, &mut vertex_gradients, biases, slopes);
+
+    let unit_value = utils::neg_unit_to_unit(utils::neg_smoothstep(vxyz * PERLIN_BIAS_3D));
+
+    let slope = utils::sigmoid_derivative(-1.5, unit_value)
+        * 0.5
+        * utils::neg_smoothstep_derivative(vxyz * PERLIN_BIAS_3D)
+        * PERLIN_BIAS_3D;
+
+    (utils::sigmoid(-1.5, unit_value), gradient * slope)
+}
+
+fn gradient_3d(rng: StatelessRand, ax: f64, ay: f64, az: f64) -> (f64, f64, f64) {
     let hash = rng.hash_3u64(ax.to_bits(), ay.to_bits(), az.to_bits()) as usize;
-    let (gx, gy, gz) = GRADIENTS_3D[hash.rotate_left(4) & 15];
 
+    GRADIENTS_3D[hash.rotate_left(4) & 15]
+}
+
+fn vertex_3d((gx, gy, gz): (f64, f64, f64), nx: f64, ny: f64, nz: f64) -> f64 {
     gx * nx + gy * ny + gz * nz
 }
@@ -0,0 +1,268 @@
+use crate::geometry::RealPoint;
+use crate::random::StatelessRand;
+use crate::utils;
+use super::gradient::*;
+
+const SKEW_2D: f64 = 0.3660254037844386;
+const UNSKEW_2D: f64 = 0.21132486540518708;
+
+const SKEW_3D: f64 = 1.0 / 3.0;
+const UNSKEW_3D: f64 = 1.0 / 6.0;
+
+const SKEW_4D: f64 = 0.30901699437494745;
+const UNSKEW_4D: f64 = 0.13819660112501053;
+
+// Squared radius of each vertex's kernel. At 0.5 the kernels never reach past the edges of the
+// simplex containing the point, so only its DIM + 1 vertices need to be summed
+pub const SIMPLEX_RADIUS: f64 = 0.5;
+
+// Multipliers that scale N-dimensional simplex noise with unit gradients to [-1.0, 1.0], found as
+// the reciprocal of the largest value it can reach
+
+// In 1D the two vertices sit only 1 / sqrt(2) apart, so both kernels overlap at their peaks:
+// 1 / max |k(x) - k(x - 1 / sqrt(2))| with k(x) = (0.5 - x^2)^4 * x, found numerically
+pub const SIMPLEX_BIAS_1D: f64 = 71.51;
+
+pub const SIMPLEX_BIAS_2D: f64 = 99.20;
+pub const SIMPLEX_BIAS_3D: f64 = 107.65;
+pub const SIMPLEX_BIAS_4D: f64 = 108.56;
+
+// From 5D on, the largest value is close to the peak of a single kernel, reached at x^2 = 1 / 18:
+// 1 / ((4 / 9)^4 / sqrt(18)) = 108.734..., rounded down so outputs stay within range
+pub const SIMPLEX_BIAS_HIGH_DIM: f64 = 108.70;
+
+// Contribution of a vertex to a point `offset` away from it. The gradient is only looked up for
+// vertices whose kernel reaches the point
+pub fn simplex_vertex<const DIM: usize>(offset: RealPoint<DIM>, gradient: impl FnOnce() -> RealPoint<DIM>) -> f64 {
+    let falloff = SIMPLEX_RADIUS - offset.dot_product(offset);
+
+    if falloff <= 0.0 {
+        return 0.0;
+    };
+
+    falloff.powi(4) * gradient().dot_product(offset)
+}
+
+pub fn simplex_vertex_with_gradient<const DIM: usize>(offset: RealPoint<DIM>, gradient: impl FnOnce() -> RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+    let falloff = SIMPLEX_RADIUS - offset.dot_product(offset);
+
+    if falloff <= 0.0 {
+        return (0.0, RealPoint::<DIM>::origin());
+    };
+
+    let gradient = gradient();
+    let projection = gradient.dot_product(offset);
+    let falloff_cubed = falloff.powi(3);
+
+    // d/dp (f^4 * g.p) with f = r - p.p is f^4 * g - 8 * f^3 * (g.p) * p
+    let value_gradient = gradient * (falloff_cubed * falloff) - offset * (8.0 * falloff_cubed * projection);
+
+    (falloff.powi(4) * projection, value_gradient)
+}
+
+// Sums the contributions of a simplex's vertices, given as (vertex, offset) pairs, and rescales
+// them to [0.0, 1.0] along with their gradient
+fn sum_with_gradient<const DIM: usize>(vertices: &[(RealPoint<DIM>, RealPoint<DIM>)], bias: f64, gradient: impl Fn(RealPoint<DIM>) -> RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+    let mut value = 0.0;
+    let mut value_gradient = RealPoint::<DIM>::origin();
+
+    for &(vertex, offset) in vertices.iter() {
+        let (vertex_value, vertex_gradient) = simplex_vertex_with_gradient(offset, || gradient(vertex));
+
+        value += vertex_value;
+        value_gradient += vertex_gradient;
+    };
+
+    (utils::neg_unit_to_unit(value * bias), value_gradient * (0.5 * bias))
+}
+
+ //-------------------------------------------------------------------------------------------------
+// 2D simplex noise
+
+pub fn simplex_2d(rng: StatelessRand, point: RealPoint<2>) -> f64 {
+    let value: f64 = simplex_2d_vertices(point).iter().map(|&(vertex, offset)| {
+        simplex_vertex(offset, || gradient_2d(rng, vertex))
+    }).sum();
+
+    utils::neg_unit_to_unit(value * SIMPLEX_BIAS_2D)
+}
+
+pub fn simplex_2d_with_gradient(rng: StatelessRand, point: RealPoint<2>) -> (f64, RealPoint<2>) {
+    sum_with_gradient(&simplex_2d_vertices(point), SIMPLEX_BIAS_2D, |vertex| gradient_2d(rng, vertex))
+}
+
+// Vertices of the triangle containing the point, and the point's offset from each of them
+fn simplex_2d_vertices(point: RealPoint<2>) -> [(RealPoint<2>, RealPoint<2>); 3] {
+    let px = point[0];
+    let py = point[1];
+
+    let skew = (px + py) * SKEW_2D;
+
+    let ax0 = (px + skew).floor();
+    let ay0 = (py + skew).floor();
+
+    let unskew = (ax0 + ay0) * UNSKEW_2D;
+
+    let nx0 = px - ax0 + unskew;
+    let ny0 = py - ay0 + unskew;
+
+    // The lower triangle has its middle vertex along x, the upper triangle along y
+    let (ix1, iy1) = if nx0 > ny0 { (1.0, 0.0) } else { (0.0, 1.0) };
+
+    let nx1 = nx0 - ix1 + UNSKEW_2D;
+    let ny1 = ny0 - iy1 + UNSKEW_2D;
+
+    let nx2 = nx0 - 1.0 + 2.0 * UNSKEW_2D;
+    let ny2 = ny0 - 1.0 + 2.0 * UNSKEW_2D;
+
+    [
+        (RealPoint::<2>::new([ax0, ay0]), RealPoint::<2>::new([nx0, ny0])),
+        (RealPoint::<2>::new([ax0 + ix1, ay0 + iy1]), RealPoint::<2>::new([nx1, ny1])),
+        (RealPoint::<2>::new([ax0 + 1.0, ay0 + 1.0]), RealPoint::<2>::new([nx2, ny2]))
+    ]
+}
+
+fn gradient_2d(rng: StatelessRand, vertex: RealPoint<2>) -> RealPoint<2> {
+    let hash = rng.hash_2u64(vertex[0].to_bits(), vertex[1].to_bits()) as usize;
+    let (gx, gy) = GRADIENTS_2D[hash & 31];
+
+    RealPoint::<2>::new([gx, gy])
+}
+
+ //-------------------------------------------------------------------------------------------------
+// 3D simplex noise
+
+pub fn simplex_3d(rng: StatelessRand, point: RealPoint<3>) -> f64 {
+    let value: f64 = simplex_3d_vertices(point).iter().map(|&(vertex, offset)| {
+        simplex_vertex(offset, || gradient_3d(rng, vertex))
+    }).sum();
+
+    utils::neg_unit_to_unit(value * SIMPLEX_BIAS_3D)
+}
+
+pub fn simplex_3d_with_gradient(rng: StatelessRand, point: RealPoint<3>) -> (f64, RealPoint<3>) {
+    sum_with_gradient(&simplex_3d_vertices(point), SIMPLEX_BIAS_3D, |vertex| gradient_3d(rng, vertex))
+}
+
+fn simplex_3d_vertices(point: RealPoint<3>) -> [(RealPoint<3>, RealPoint<3>); 4] {
+    let px = point[0];
+    let py = point[1];
+    let pz = point[2];
+
+    let skew = (px + py + pz) * SKEW_3D;
+
+    let ax0 = (px + skew).floor();
+    let ay0 = (py + skew).floor();
+    let az0 = (pz + skew).floor();
+
+    let unskew = (ax0 + ay0 + az0) * UNSKEW_3D;
+
+    let nx0 = px - ax0 + unskew;
+    let ny0 = py - ay0 + unskew;
+    let nz0 = pz - az0 + unskew;
+
+    // Each of the six tetrahedra in the skewed cube is picked by the ordering of the offsets
+    let ((ix1, iy1, iz1), (ix2, iy2, iz2)) = if nx0 >= ny0 {
+        if ny0 >= nz0 {
+            ((1.0, 0.0, 0.0), (1.0, 1.0, 0.0))
+        } else if nx0 >= nz0 {
+            ((1.0, 0.0, 0.0), (1.0, 0.0, 1.0))
+        } else {
+            ((0.0, 0.0, 1.0), (1.0, 0.0, 1.0))
+        }
+    } else if ny0 < nz0 {
+        ((0.0, 0.0, 1.0), (0.0, 1.0, 1.0))
+    } else if nx0 < nz0 {
+        ((0.0, 1.0, 0.0), (0.0, 1.0, 1.0))
+    } else {
+        ((0.0, 1.0, 0.0), (1.0, 1.0, 0.0))
+    };
+
+    let nx1 = nx0 - ix1 + UNSKEW_3D;
+    let ny1 = ny0 - iy1 + UNSKEW_3D;
+    let nz1 = nz0 - iz1 + UNSKEW_3D;
+
+    let nx2 = nx0 - ix2 + 2.0 * UNSKEW_3D;
+    let ny2 = ny0 - iy2 + 2.0 * UNSKEW_3D;
+    let nz2 = nz0 - iz2 + 2.0 * UNSKEW_3D;
+
+    let nx3 = nx0 - 1.0 + 3.0 * UNSKEW_3D;
+    let ny3 = ny0 - 1.0 + 3.0 * UNSKEW_3D;
+    let nz3 = nz0 - 1.0 + 3.0 * UNSKEW_3D;
+
+    [
+        (RealPoint::<3>::new([ax0, ay0, az0]), RealPoint::<3>::new([nx0, ny0, nz0])),
+        (RealPoint::<3>::new([ax0 + ix1, ay0 + iy1, az0 + iz1]), RealPoint::<3>::new([nx1, ny1, nz1])),
+        (RealPoint::<3>::new([ax0 + ix2, ay0 + iy2, az0 + iz2]), RealPoint::<3>::new([nx2, ny2, nz2])),
+        (RealPoint::<3>::new([ax0 + 1.0, ay0 + 1.0, az0 + 1.0]), RealPoint::<3>::new([nx3, ny3, nz3]))
+    ]
+}
+
+fn gradient_3d(rng: StatelessRand, vertex: RealPoint<3>) -> RealPoint<3> {
+    let hash = rng.hash_3u64(vertex[0].to_bits(), vertex[1].to_bits(), vertex[2].to_bits()) as usize;
+    let (gx, gy, gz) = GRADIENTS_3D[hash & 63];
+
+    // `GRADIENTS_3D` only covers the upper hemisphere, so another bit of the hash picks the side
+    let sign = if hash & 64 == 0 { 1.0 } else { -1.0 };
+
+    RealPoint::<3>::new([gx, gy, gz]) * sign
+}
+
+ //-------------------------------------------------------------------------------------------------
+// 4D simplex noise
+
+pub fn simplex_4d(rng: StatelessRand, point: RealPoint<4>) -> f64 {
+    let value: f64 = simplex_4d_vertices(point).iter().map(|&(vertex, offset)| {
+        simplex_vertex(offset, || gradient_4d(rng, vertex))
+    }).sum();
+
+    utils::neg_unit_to_unit(value * SIMPLEX_BIAS_4D)
+}
+
+pub fn simplex_4d_with_gradient(rng: StatelessRand, point: RealPoint<4>) -> (f64, RealPoint<4>) {
+    sum_with_gradient(&simplex_4d_vertices(point), SIMPLEX_BIAS_4D, |vertex| gradient_4d(rng, vertex))
+}
+
+fn simplex_4d_vertices(point: RealPoint<4>) -> [(RealPoint<4>, RealPoint<4>); 5] {
+    let skew = point.sum() * SKEW_4D;
+    let cell = (point + skew).floor();
+    let unskew = cell.sum() * UNSKEW_4D;
+
+    let offset = point - cell + unskew;
+
+    // The rank of each axis among the offsets decides at which step it is incremented
+    let mut ranks = [0_usize; 4];
+
+    for lhs in 0..4 {
+        for rhs in (lhs + 1)..4 {
+            if offset[lhs] >= offset[rhs] {
+                ranks[lhs] += 1;
+            } else {
+                ranks[rhs] += 1;
+            };
+        };
+    };
+
+    let mut vertices = [(cell, offset); 5];
+
+    for (step, vertex) in vertices.iter_mut().enumerate() {
+        let mut increment = RealPoint::<4>::origin();
+
+        for (dim, &rank) in ranks.iter().enumerate() {
+            if rank + step >= 4 {
+                increment[dim] = 1.0;
+            };
+        };
+
+        *vertex = (cell + increment, offset - increment + (step as f64) * UNSKEW_4D);
+    };
+
+    vertices
+}
+
+fn gradient_4d(rng: StatelessRand, vertex: RealPoint<4>) -> RealPoint<4> {
+    let hash = rng.hash_bytes(vertex.as_bytes()) as usize;
+    let (gx, gy, gz, gw) = GRADIENTS_4D[hash & 31];
+
+    RealPoint::<4>::new([gx, gy, gz, gw])
+}
@@ -0,0 +1,237 @@
+use std::f64::consts::PI;
+
+use rand_distr::{Poisson, StandardNormal};
+
+use crate::geometry::{EuclideanMetric, LatticePoint, RealPoint};
+use crate::random::{StatefulRand, StatelessRand};
+use crate::utils;
+use super::{ConstNode, NoiseNode, VectorNoiseNode};
+
+#[derive(Clone)]
+pub enum GaborOrientation<const DIM: usize, Field>
+where Field: VectorNoiseNode<DIM> {
+    // Every kernel gets its own uniformly random direction, giving isotropic noise
+    Random,
+
+    // Every kernel points the same way, giving stripes across `direction`
+    Fixed(RealPoint<DIM>),
+
+    // Each kernel points along the field at its impulse, falling back to a random direction
+    // wherever the field vanishes
+    Field(Field)
+}
+
+// Sparse convolution noise: kernels, each a Gaussian times a cosine wave, are scattered over
+// lattice cells one kernel radius wide and summed. `frequency` sets the wavelength of the cosine
+// and `bandwidth` the width of the Gaussian, so a low bandwidth relative to the frequency gives
+// long, straight grain
+#[derive(Clone)]
+pub struct GaborNode<const DIM: usize, Field>
+where Field: VectorNoiseNode<DIM> {
+    stateless_rng: StatelessRand,
+    stateful_rng: StatefulRand,
+    frequency: f64,
+    bandwidth: f64,
+    impulse_density: f64,
+    impulse_sampler: Poisson<f64>,
+    orientation: GaborOrientation<DIM, Field>
+}
+
+impl<const DIM: usize> GaborNode<DIM, [ConstNode; DIM]> {
+    pub fn new(seed: u64, frequency: f64, bandwidth: f64) -> Self {
+        assert!(bandwidth > 0.0, "gabor kernels need a positive bandwidth");
+
+        Self {
+            stateless_rng: StatelessRand::from_seed(seed),
+            stateful_rng: StatefulRand::from_seed(seed),
+            frequency,
+            bandwidth,
+            impulse_density: 8.0,
+            impulse_sampler: Poisson::new(8.0).unwrap(),
+            orientation: GaborOrientation::Random
+        }
+    }
+}
+
+impl<const DIM: usize, Field> GaborNode<DIM, Field>
+where Field: VectorNoiseNode<DIM> {
+    pub fn with_direction(mut self, direction: RealPoint<DIM>) -> Self {
+        assert!(direction.magnitude::<EuclideanMetric>() > 0.0, "gabor kernels need a non-zero direction");
+
+        self.orientation = GaborOrientation::Fixed(direction.normalize());
+        self
+    }
+
+    // Steers the kernels by a vector field, such as an array of scalar nodes
+    pub fn with_orientation_field<NewField>(self, field: NewField) -> GaborNode<DIM, NewField>
+    where NewField: VectorNoiseNode<DIM> {
+        GaborNode {
+            stateless_rng: self.stateless_rng,
+            stateful_rng: self.stateful_rng,
+            frequency: self.frequency,
+            bandwidth: self.bandwidth,
+            impulse_density: self.impulse_density,
+            impulse_sampler: self.impulse_sampler,
+            orientation: GaborOrientation::Field(field)
+        }
+    }
+
+    // Mean number of kernels per cell. Sparse kernels show up as separate blobs, while dense ones
+    // cost more to evaluate
+    pub fn with_impulse_density(mut self, impulse_density: f64) -> Self {
+        assert!(impulse_density > 0.0 && impulse_density.is_finite(), "gabor noise needs a positive, finite impulse density");
+
+        self.impulse_density = impulse_density;
+        self.impulse_sampler = Poisson::new(impulse_density).unwrap();
+        self
+    }
+
+    fn direction(&self, rng: &mut StatefulRand, impulse: RealPoint<DIM>) -> RealPoint<DIM> {
+        let direction = match &self.orientation {
+            GaborOrientation::Random => RealPoint::<DIM>::origin(),
+            GaborOrientation::Fixed(direction) => return *direction,
+            GaborOrientation::Field(field) => field.vector_at(impulse / self.bandwidth)
+        };
+
+        if direction.magnitude::<EuclideanMetric>() > 0.0 {
+            return direction.normalize();
+        };
+
+        // Normally distributed coordinates point uniformly in every direction
+        let mut direction = RealPoint::<DIM>::origin();
+
+        for coord in direction.iter_mut() {
+            *coord = rng.sample(StandardNormal);
+        };
+
+        direction.normalize()
+    }
+
+    // Sum of the kernels in one cell, where `point` and the kernels are measured in cells
+    fn cell_value(&self, cell: LatticePoint<DIM>, point: RealPoint<DIM>) -> f64 {
+        let real_cell = cell.to_real_point();
+        let hash = self.stateless_rng.hash_bytes(cell.as_bytes());
+
+        let mut rng = self.stateful_rng.clone();
+
+        rng.set_word_pos(0);
+        rng.set_stream(hash);
+
+        let num_impulses = rng.sample(self.impulse_sampler) as usize;
+        let cycles_per_cell = self.frequency / self.bandwidth;
+
+        (0..num_impulses).fold(0.0, |value, _| {
+            let mut impulse = real_cell;
+
+            for coord in impulse.iter_mut() {
+                *coord += rng.get_f64();
+            };
+
+            let weight = utils::unit_to_neg_unit(rng.get_f64());
+            let direction = self.direction(&mut rng, impulse);
+            let offset = point - impulse;
+
+            let squared_distance = offset.dot_product(offset);
+
+            if squared_distance >= 1.0 {
+                return value;
+            };
+
+            let envelope = envelope(squared_distance);
+            let wave = (2.0 * PI * cycles_per_cell * direction.dot_product(offset)).cos();
+
+            value + weight * envelope * wave
+        })
+    }
+}
+
+// A Gaussian over the squared distance from a kernel's centre, shifted and rescaled so it falls to
+// exactly 0.0 at the kernel radius and kernels don't leave a step where they're cut off
+fn envelope(squared_distance: f64) -> f64 {
+    let edge = (-PI).exp();
+
+    ((-PI * squared_distance).exp() - edge) / (1.0 - edge)
+}
+
+impl<const DIM: usize, Field> NoiseNode<DIM> for GaborNode<DIM, Field>
+where Field: VectorNoiseNode<DIM> {
+    // Weights uniform in [-1.0, 1.0] give the sum a variance of density / 3 times the integral of
+    // a squared kernel, roughly (1 / 2) * 2^(-N / 2). Three standard deviations span the output
+    // range, and the rare values beyond are clamped
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let cell_point = point * self.bandwidth;
+
+        let value = cell_point.to_lattice_point().neighbors_and_self().fold(0.0, |value, cell| {
+            value + self.cell_value(cell, cell_point)
+        });
+
+        let kernel_energy = 0.5 * 2.0_f64.powf(-(DIM as f64) / 2.0);
+        let deviation = (self.impulse_density / 3.0 * kernel_energy).sqrt();
+
+        utils::neg_unit_to_unit((value / (3.0 * deviation)).clamp(-1.0, 1.0))
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::NoiseNode;
+    use super::{envelope, GaborNode};
+
+    #[test]
+    fn directional() {
+        let node = GaborNode::<2, _>::new(1, 4.0, 0.5).with_direction(RealPoint::<2>::new([1.0, 0.0]));
+
+        let mut along = 0.0;
+        let mut across = 0.0;
+        let mut total = 0.0;
+
+        for idx in 0..1000 {
+            let t = idx as f64;
+            let point = RealPoint::<2>::new([t * 0.173 - 50.0, t * 0.311 - 80.0]);
+            let value = node.value_at(point);
+
+            along += (node.value_at(point + RealPoint::<2>::new([0.05, 0.0])) - value).abs();
+            across += (node.value_at(point + RealPoint::<2>::new([0.0, 0.05])) - value).abs();
+            total += value;
+        };
+
+        // Stripes run across the direction, so values change far faster along it
+        assert!(along > 4.0 * across, "{} vs {}", along, across);
+        assert!((total / 1000.0 - 0.5).abs() < 0.05, "mean {}", total / 1000.0);
+    }
+
+    #[test]
+    fn continuous() {
+        assert_eq!(envelope(0.0), 1.0);
+        assert!(envelope(1.0 - 1e-12).abs() < 1e-10);
+
+        // Cells are 1 / bandwidth wide, so these pairs straddle cell boundaries along each axis
+        let node = GaborNode::<2, _>::new(1, 4.0, 0.5).with_impulse_density(2.0);
+
+        for idx in 0..200 {
+            let t = (idx as f64) * 0.137 - 13.0;
+
+            for boundary in [-4.0, 0.0, 6.0] {
+                let before = node.value_at(RealPoint::<2>::new([boundary - 1e-9, t]));
+                let after = node.value_at(RealPoint::<2>::new([boundary + 1e-9, t]));
+
+                assert!((before - after).abs() < 1e-6, "{} vs {} at ({}, {})", before, after, boundary, t);
+
+                let before = node.value_at(RealPoint::<2>::new([t, boundary - 1e-9]));
+                let after = node.value_at(RealPoint::<2>::new([t, boundary + 1e-9]));
+
+                assert!((before - after).abs() < 1e-6, "{} vs {} at ({}, {})", before, after, t, boundary);
+            };
+        };
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero direction")]
+    fn zero_direction() {
+        GaborNode::<2, _>::new(1, 4.0, 0.5).with_direction(RealPoint::<2>::origin());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+// Regular grids are laid out with the first axis varying fastest, so each run of `extent[0]`
+// values is a row along x
+
+pub fn num_points<const DIM: usize>(extent: [usize; DIM]) -> usize {
+    extent.iter().product()
+}
+
+pub fn grid_index<const DIM: usize>(extent: [usize; DIM], mut flat_index: usize) -> [usize; DIM] {
+    let mut index = [0_usize; DIM];
+
+    for (axis_index, &axis_extent) in index.iter_mut().zip(extent.iter()) {
+        *axis_index = flat_index % axis_extent;
+        flat_index /= axis_extent;
+    };
+
+    index
+}
+
+// Index into the whole grid of the point `flat_index` values into a section starting at `start`
+pub fn section_index<const DIM: usize>(start: [usize; DIM], extent: [usize; DIM], flat_index: usize) -> [usize; DIM] {
+    let mut index = grid_index(extent, flat_index);
+
+    for (axis_index, &axis_start) in index.iter_mut().zip(start.iter()) {
+        *axis_index += axis_start;
+    };
+
+    index
+}
+
+pub fn grid_coordinate(origin: f64, step: f64, index: usize) -> f64 {
+    origin + (index as f64) * step
+}
+
+pub fn grid_point<const DIM: usize>(origin: RealPoint<DIM>, step: RealPoint<DIM>, index: [usize; DIM]) -> RealPoint<DIM> {
+    let mut coordinates = [0.0_f64; DIM];
+
+    for (dim, coord) in coordinates.iter_mut().enumerate() {
+        *coord = grid_coordinate(origin[dim], step[dim], index[dim]);
+    };
+
+    RealPoint::<DIM>::new(coordinates)
+}
+
+// The default `NoiseNode::fill_grid_section`, evaluating every point independently
+pub fn fill_per_point<const DIM: usize, Node>(node: &Node, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64])
+where Node: NoiseNode<DIM> + ?Sized {
+    assert_eq!(output.len(), num_points(extent));
+
+    for (flat_index, value) in output.iter_mut().enumerate() {
+        let index = section_index(start, extent, flat_index);
+
+        *value = node.value_at(grid_point(origin, step, index));
+    };
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{EuclideanMetric, RealPoint};
+    use crate::noise::{NoiseNode, PeriodicNode, PerlinNode, StaticNode, TileNode, WorleyNode, WorleyPaintMethod};
+    use super::{fill_per_point, grid_index, num_points};
+
+    fn assert_matches_per_point<const DIM: usize>(node: &impl NoiseNode<DIM>, origin: [f64; DIM], step: [f64; DIM], extent: [usize; DIM]) {
+        let origin = RealPoint::<DIM>::new(origin);
+        let step = RealPoint::<DIM>::new(step);
+
+        let mut expected = vec![0.0; num_points(extent)];
+        let mut actual = vec![0.0; num_points(extent)];
+
+        fill_per_point(node, origin, step, [0; DIM], extent, &mut expected);
+        node.fill_grid(origin, step, extent, &mut actual);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn index_layout() {
+        assert_eq!(grid_index([4, 3, 2], 0), [0, 0, 0]);
+        assert_eq!(grid_index([4, 3, 2], 5), [1, 1, 0]);
+        assert_eq!(grid_index([4, 3, 2], 23), [3, 2, 1]);
+    }
+
+    #[test]
+    fn overrides_match_per_point() {
+        assert_matches_per_point(&PerlinNode::<2>::new(1), [-2.3, 1.7], [0.15, 0.2], [24, 16]);
+        assert_matches_per_point(&PerlinNode::<3>::new(1), [-2.3, 1.7, 0.4], [0.15, 0.2, 0.35], [12, 8, 6]);
+        assert_matches_per_point(&PerlinNode::<2>::new(1).with_period([2, 3]), [-2.3, 1.7], [0.15, 0.2], [24, 16]);
+        assert_matches_per_point(&StaticNode::<2>::new(1, 0.0, 1.0), [-2.3, 1.7], [0.15, 0.2], [24, 16]);
+        assert_matches_per_point(&StaticNode::<3>::new(1, 0.0, 1.0), [-2.3, 1.7, 0.4], [0.15, 0.2, 0.35], [12, 8, 6]);
+        assert_matches_per_point(&TileNode::<3>::new(1), [-2.3, 1.7, 0.4], [0.15, 0.2, 0.35], [12, 8, 6]);
+
+        let worley = WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::F2MinusF1);
+
+        assert_matches_per_point(&worley, [-2.3, 1.7], [0.15, -0.2], [24, 16]);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::geometry::RealPoint;
+use super::{grid, NoiseNode};
+use super::function::{lerp_vertices, lerp_vertices_with_gradient};
+
+// Cells are interpolated in buffers on the stack, sized for this many dimensions
+const MAX_DIM: usize = 8;
+
+// Samples a source once over a bounded region, at `resolution` points along each axis from `min`
+// to `max`, and answers queries by multilinear interpolation between the samples. Points outside
+// the region take the value at its nearest edge
+#[derive(Clone)]
+pub struct GridCacheNode<const DIM: usize> {
+    origin: RealPoint<DIM>,
+    step: RealPoint<DIM>,
+    extent: [usize; DIM],
+    values: Vec<f64>
+}
+
+impl<const DIM: usize> GridCacheNode<DIM> {
+    pub fn new<Source>(source: &Source, min: RealPoint<DIM>, max: RealPoint<DIM>, resolution: [usize; DIM]) -> Self
+    where Source: NoiseNode<DIM> + ?Sized {
+        assert!(DIM <= MAX_DIM, "grid caches support at most {} dimensions", MAX_DIM);
+        assert!(resolution.iter().all(|&points| points >= 2), "grid caches need at least two points along every axis");
+        assert!((0..DIM).all(|dim| min[dim] < max[dim]), "grid caches need min < max along every axis");
+
+        let mut step = max - min;
+
+        for (coord, &points) in step.iter_mut().zip(resolution.iter()) {
+            *coord /= (points - 1) as f64;
+        };
+
+        let mut values = vec![0.0; grid::num_points(resolution)];
+
+        source.fill_grid(min, step, resolution, &mut values);
+
+        Self { origin: min, step, extent: resolution, values }
+    }
+
+    // The lowest sample of the grid cell holding `point`, and how far into the cell it lies along
+    // each axis, along with that offset's slope (zero where the point was clamped)
+    fn locate(&self, point: RealPoint<DIM>) -> ([usize; DIM], RealPoint<DIM>, RealPoint<DIM>) {
+        let mut cell = [0_usize; DIM];
+        let mut biases = RealPoint::<DIM>::origin();
+        let mut slopes = RealPoint::<DIM>::origin();
+
+        for dim in 0..DIM {
+            let last = (self.extent[dim] - 1) as f64;
+            let position = (point[dim] - self.origin[dim]) / self.step[dim];
+            let clamped = position.clamp(0.0, last);
+
+            let lower = clamped.floor().min(last - 1.0);
+
+            cell[dim] = lower as usize;
+            biases[dim] = clamped - lower;
+            slopes[dim] = if position == clamped { self.step[dim].recip() } else { 0.0 };
+        };
+
+        (cell, biases, slopes)
+    }
+
+    // Writes the samples at the 2^N vertices of a cell to the start of `output`, in the order of
+    // `VertexNeighborhood`, and returns that part of it
+    fn cell_values<'a>(&self, cell: [usize; DIM], output: &'a mut [f64]) -> &'a mut [f64] {
+        let cell_values = &mut output[..(1 << DIM)];
+
+        for (vertex, value) in cell_values.iter_mut().enumerate() {
+            let mut flat_index = 0;
+
+            for dim in (0..DIM).rev() {
+                flat_index = flat_index * self.extent[dim] + cell[dim] + ((vertex >> dim) & 1);
+            };
+
+            *value = self.values[flat_index];
+        };
+
+        cell_values
+    }
+}
+
+impl<const DIM: usize> NoiseNode<DIM> for GridCacheNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let (cell, biases, _) = self.locate(point);
+        let mut buffer = [0.0; 1 << MAX_DIM];
+
+        lerp_vertices(self.cell_values(cell, &mut buffer), biases)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (cell, biases, slopes) = self.locate(point);
+        let mut buffer = [0.0; 1 << MAX_DIM];
+        let mut gradients = [RealPoint::<DIM>::origin(); 1 << MAX_DIM];
+
+        let values = self.cell_values(cell, &mut buffer);
+        let num_vertices = values.len();
+
+        lerp_vertices_with_gradient(values, &mut gradients[..num_vertices], biases, slopes)
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::{LinearGradientNode, NoiseNode, SimplexNode};
+    use super::GridCacheNode;
+
+    #[test]
+    fn interpolates_samples() {
+        let source = SimplexNode::<2>::new(1);
+        let cache = GridCacheNode::new(&source, RealPoint::<2>::new([-2.0, 1.0]), RealPoint::<2>::new([2.0, 3.0]), [41, 21]);
+
+        // Samples come back exactly, everything else within the error of linear interpolation
+        assert_eq!(cache.value_at(RealPoint::<2>::new([-2.0, 1.0])), source.value_at(RealPoint::<2>::new([-2.0, 1.0])));
+        assert_eq!(cache.value_at(RealPoint::<2>::new([2.0, 3.0])), source.value_at(RealPoint::<2>::new([2.0, 3.0])));
+
+        for idx in 0..100 {
+            let point = RealPoint::<2>::new([(idx as f64) * 0.039 - 1.9, (idx as f64) * 0.017 + 1.1]);
+
+            assert!((cache.value_at(point) - source.value_at(point)).abs() < 0.05);
+        };
+
+        // A linear source is reproduced exactly inside the region, and clamped outside it
+        let ramp = LinearGradientNode::new(RealPoint::<2>::new([0.0, 0.0]), RealPoint::<2>::new([4.0, 0.0]));
+        let ramp_cache = GridCacheNode::new(&ramp, RealPoint::<2>::new([0.0, 0.0]), RealPoint::<2>::new([4.0, 1.0]), [5, 2]);
+
+        assert!((ramp_cache.value_at(RealPoint::<2>::new([1.3, 0.6])) - 0.325).abs() < 1e-12);
+        assert_eq!(ramp_cache.value_at(RealPoint::<2>::new([9.0, -4.0])), 1.0);
+        assert_eq!(ramp_cache.value_and_gradient(RealPoint::<2>::new([1.3, 0.6])).1, RealPoint::<2>::new([0.25, 0.0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "min < max")]
+    fn empty_region() {
+        let source = SimplexNode::<2>::new(1);
+
+        GridCacheNode::new(&source, RealPoint::<2>::new([0.0, 1.0]), RealPoint::<2>::new([1.0, 1.0]), [4, 4]);
+    }
+}
@@ -1,38 +1,381 @@
-use crate::geometry::RealPoint;
-use super::NoiseNode;
+use crate::geometry::{LinearMap, RealPoint};
+use crate::random::StatelessRand;
+use crate::utils;
+use super::{derivative, NoiseNode, PeriodicNode};
 
-pub struct HarmonicNode<'a, const DIM: usize, Source>
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FractalKind {
+    // Plain fractional Brownian motion, the weighted average of every octave
+    Fbm,
+
+    // Folds every octave around its midpoint, giving rounded hills and sharp creases
+    Billow,
+
+    // Inverted billow, where each octave is damped wherever the previous one was low, so detail
+    // gathers along the ridges
+    Ridged,
+
+    // Musgrave's hybrid multifractal: each octave is weighted by the octaves before it, so low
+    // areas stay smooth while high ones get rough
+    HybridMultifractal,
+
+    // Musgrave's heterogeneous terrain: each octave is scaled by the height accumulated so far
+    HeterogeneousMultifractal
+}
+
+// How strongly a ridged or hybrid octave weights the next one
+const MULTIFRACTAL_GAIN: f64 = 2.0;
+
+// Octave offsets are drawn from [0.0, OCTAVE_OFFSET_RANGE) along every axis
+const OCTAVE_OFFSET_RANGE: f64 = 256.0;
+
+#[derive(Clone)]
+pub struct HarmonicNode<const DIM: usize, Source>
 where Source: NoiseNode<DIM> {
-    source: &'a Source,
+    source: Source,
     num_octaves: usize,
+    last_octave_weight: f64,
     persistence: f64,
-    lacunarity: f64
+    lacunarity: f64,
+    kind: FractalKind,
+    offset_rng: Option<StatelessRand>,
+    octave_offsets: Vec<RealPoint<DIM>>,
+    octave_rotation: Option<LinearMap<DIM>>,
+    octave_sources: Vec<Source>
 }
 
-impl<'a, const DIM: usize, Source> HarmonicNode<'a, DIM, Source>
+impl<const DIM: usize, Source> HarmonicNode<DIM, Source>
 where Source: NoiseNode<DIM> {
-    pub fn new(source: &'a Source, num_octaves: usize, persistence: f64, lacunarity: f64) -> Self {
-        Self { source, num_octaves, persistence, lacunarity }
+    pub fn new(source: Source, num_octaves: usize, persistence: f64, lacunarity: f64) -> Self {
+        Self {
+            source,
+            num_octaves,
+            last_octave_weight: 1.0,
+            persistence,
+            lacunarity,
+            kind: FractalKind::Fbm,
+            offset_rng: None,
+            octave_offsets: Vec::with_capacity(0),
+            octave_rotation: None,
+            octave_sources: Vec::with_capacity(0)
+        }
+    }
+
+    // Rounds the octave count up and fades the last octave in by the fractional part, so detail
+    // can be added continuously as the count grows
+    pub fn with_fractional_octaves(mut self, num_octaves: f64) -> Self {
+        assert!(num_octaves > 0.0, "harmonics need a positive octave count");
+        assert!(!self.is_periodic(), "set the octave count before the period");
+
+        let fract = num_octaves.fract();
+
+        self.num_octaves = num_octaves.ceil() as usize;
+        self.last_octave_weight = if fract > 0.0 { fract } else { 1.0 };
+        self.build_octave_offsets();
+        self
+    }
+
+    pub fn with_kind(mut self, kind: FractalKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    // Shifts every octave by its own pseudo-random offset, so the lattices of different octaves
+    // stop lining up at the origin
+    pub fn with_octave_offsets(mut self, seed: u64) -> Self {
+        self.offset_rng = Some(StatelessRand::from_seed(seed));
+        self.build_octave_offsets();
+        self
+    }
+
+    // Rotates every octave once more than the one before it, so axis-aligned artifacts of the
+    // source don't reinforce each other. Any `LinearMap` works, though rotations keep the
+    // frequency of every octave intact
+    pub fn with_octave_rotation(mut self, rotation: LinearMap<DIM>) -> Self {
+        assert!(!self.is_periodic(), "periodic harmonics can't rotate their octaves");
+
+        self.octave_rotation = Some(rotation);
+        self
+    }
+
+    // Periodic harmonics hold a copy of the source for every octave
+    fn is_periodic(&self) -> bool {
+        !self.octave_sources.is_empty()
+    }
+
+    fn octave_source(&self, octave: usize) -> &Source {
+        self.octave_sources.get(octave).unwrap_or(&self.source)
+    }
+
+    // Offsets only depend on the seed and the octave, so they're drawn up front whenever the
+    // octave count changes rather than on every sample
+    fn build_octave_offsets(&mut self) {
+        let rng = match self.offset_rng {
+            Some(rng) => rng,
+            None => return
+        };
+
+        self.octave_offsets = (0..self.num_octaves).map(|octave| {
+            let mut offset = RealPoint::<DIM>::origin();
+
+            for (dim, coord) in offset.iter_mut().enumerate() {
+                let hash = rng.hash_2u64(octave as u64, dim as u64);
+
+                *coord = utils::f64_from_mantissa(hash, 0.0, OCTAVE_OFFSET_RANGE);
+            };
+
+            offset
+        }).collect();
+    }
+
+    fn octave_point(&self, octave: usize, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        match self.octave_offsets.get(octave) {
+            Some(&offset) => point + offset,
+            None => point
+        }
+    }
+
+    fn octave_weight(&self, octave: usize) -> f64 {
+        if octave + 1 == self.num_octaves { self.last_octave_weight } else { 1.0 }
+    }
+
+    fn rotate(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        match &self.octave_rotation {
+            Some(rotation) => rotation.apply(point),
+            None => point
+        }
     }
 }
 
-impl<'a, const DIM: usize, Source> NoiseNode<DIM> for HarmonicNode<'a, DIM, Source>
+impl<const DIM: usize, Source> NoiseNode<DIM> for HarmonicNode<DIM, Source>
 where Source: NoiseNode<DIM> {
+    // Every kind keeps each octave's contribution within [0.0, amplitude], so dividing by the
+    // summed amplitudes maps the result to [0.0, 1.0]
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
         let mut value: f64 = 0.0;
 
         let mut max_value: f64 = 0.0;
         let mut frequency: f64 = 1.0;
         let mut amplitude: f64 = 1.0;
+        let mut weight: f64 = 1.0;
+
+        let mut rotated_point = point;
+
+        for octave in 0..self.num_octaves {
+            let signal = self.octave_source(octave).value_at(self.octave_point(octave, rotated_point * frequency));
+            let octave_amplitude = amplitude * self.octave_weight(octave);
+
+            match self.kind {
+                FractalKind::Fbm => {
+                    value += signal * octave_amplitude;
+                },
+                FractalKind::Billow => {
+                    value += utils::unit_to_neg_unit(signal).abs() * octave_amplitude;
+                },
+                FractalKind::Ridged => {
+                    let ridge = (1.0 - utils::unit_to_neg_unit(signal).abs()).powi(2) * weight;
+
+                    value += ridge * octave_amplitude;
+                    weight = (ridge * MULTIFRACTAL_GAIN).clamp(0.0, 1.0);
+                },
+                FractalKind::HybridMultifractal => {
+                    value += signal * weight * octave_amplitude;
+                    weight = (signal * weight * MULTIFRACTAL_GAIN).min(1.0);
+                },
+                FractalKind::HeterogeneousMultifractal => {
+                    let height = if max_value > 0.0 { value / max_value } else { 1.0 };
 
-        for _ in 0..self.num_octaves {
-            value += self.source.value_at(point * frequency) * amplitude;
+                    value += signal * height * octave_amplitude;
+                }
+            };
 
-            max_value += amplitude;
+            max_value += octave_amplitude;
             amplitude *= self.persistence;
             frequency *= self.lacunarity;
+            rotated_point = self.rotate(rotated_point);
         };
 
         value / max_value
     }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        // The multifractal kinds weight octaves by one another, so only the kinds that sum
+        // independent octaves are differentiated analytically
+        if !matches!(self.kind, FractalKind::Fbm | FractalKind::Billow) {
+            return derivative::central_difference(self, point);
+        };
+
+        let mut value: f64 = 0.0;
+        let mut gradient = RealPoint::<DIM>::origin();
+
+        let mut max_value: f64 = 0.0;
+        let mut frequency: f64 = 1.0;
+        let mut amplitude: f64 = 1.0;
+
+        let mut rotated_point = point;
+
+        for octave in 0..self.num_octaves {
+            let (signal, mut signal_gradient) = self.octave_source(octave).value_and_gradient(self.octave_point(octave, rotated_point * frequency));
+            let octave_amplitude = amplitude * self.octave_weight(octave);
+
+            let (octave_value, octave_slope) = match self.kind {
+                FractalKind::Billow => {
+                    let folded = utils::unit_to_neg_unit(signal);
+
+                    (folded.abs(), 2.0 * folded.signum())
+                },
+                _ => (signal, 1.0)
+            };
+
+            // The octave was rotated `octave` times, so its gradient is rotated back as often
+            if let Some(rotation) = &self.octave_rotation {
+                for _ in 0..octave {
+                    signal_gradient = rotation.apply_transpose(signal_gradient);
+                };
+            };
+
+            value += octave_value * octave_amplitude;
+            gradient += signal_gradient * (octave_slope * octave_amplitude * frequency);
+
+            max_value += octave_amplitude;
+            amplitude *= self.persistence;
+            frequency *= self.lacunarity;
+            rotated_point = self.rotate(rotated_point);
+        };
+
+        (value / max_value, gradient / max_value)
+    }
+}
+
+// Octave N samples its source at lacunarity^N times the scale, so it gets its own copy of the
+// source whose period is scaled up to match. Octave rotations can't tile, and the octave count
+// is fixed once the period is set, so both are rejected afterwards
+impl<const DIM: usize, Source> PeriodicNode<DIM> for HarmonicNode<DIM, Source>
+where Source: PeriodicNode<DIM> + Clone {
+    fn with_period(mut self, period: [u32; DIM]) -> Self {
+        assert!((1.0..=(u32::MAX as f64)).contains(&self.lacunarity), "periodic harmonics need a lacunarity of at least 1.0");
+        assert_eq!(self.lacunarity.fract(), 0.0, "periodic harmonics need a whole lacunarity");
+        assert!(self.octave_rotation.is_none(), "periodic harmonics can't rotate their octaves");
+
+        let lacunarity = self.lacunarity as u32;
+        let mut frequency = Some(1_u32);
+
+        self.octave_sources = (0..self.num_octaves).map(|octave| {
+            let octave_period = period.map(|cells| frequency.and_then(|frequency| cells.checked_mul(frequency)));
+
+            assert!(octave_period.iter().all(Option::is_some), "periodic harmonics overflow a u32 period at octave {}", octave);
+
+            frequency = frequency.and_then(|frequency| frequency.checked_mul(lacunarity));
+
+            self.source.clone().with_period(octave_period.map(Option::unwrap))
+        }).collect();
+
+        self
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::geometry::LinearMap;
+    use crate::noise::{NoiseNode, NoiseNodeExt, PeriodicNode, PerlinNode, SimplexNode};
+    use super::FractalKind;
+
+    #[test]
+    fn normalized_kinds() {
+        let kinds = [
+            FractalKind::Fbm,
+            FractalKind::Billow,
+            FractalKind::Ridged,
+            FractalKind::HybridMultifractal,
+            FractalKind::HeterogeneousMultifractal
+        ];
+
+        for kind in kinds {
+            let node = SimplexNode::<2>::new(1).fbm(6, 0.5, 2.0).with_kind(kind).with_octave_offsets(1);
+
+            for idx in 0..1000 {
+                let t = idx as f64;
+                let value = node.value_at(RealPoint::<2>::new([t * 0.173 - 50.0, t * 0.311 - 80.0]));
+
+                assert!((0.0..=1.0).contains(&value), "{:?} gave {}", kind, value);
+            };
+        };
+    }
+
+    #[test]
+    fn fractional_octaves() {
+        let point = RealPoint::<2>::new([3.7, -1.2]);
+
+        let two = SimplexNode::<2>::new(1).fbm(2, 0.5, 2.0);
+        let three = SimplexNode::<2>::new(1).fbm(3, 0.5, 2.0);
+
+        let whole = SimplexNode::<2>::new(1).fbm(1, 0.5, 2.0).with_fractional_octaves(3.0);
+        let barely = SimplexNode::<2>::new(1).fbm(1, 0.5, 2.0).with_fractional_octaves(2.000001);
+
+        assert_eq!(whole.value_at(point), three.value_at(point));
+        assert!((barely.value_at(point) - two.value_at(point)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn offsets_follow_octave_count() {
+        let offsets_first = SimplexNode::<2>::new(1).fbm(2, 0.5, 2.0).with_octave_offsets(1).with_fractional_octaves(4.5);
+        let offsets_last = SimplexNode::<2>::new(1).fbm(2, 0.5, 2.0).with_fractional_octaves(4.5).with_octave_offsets(1);
+
+        for idx in 0..100 {
+            let point = RealPoint::<2>::new([(idx as f64) * 0.37 - 20.0, (idx as f64) * 0.11]);
+
+            assert_eq!(offsets_first.value_at(point), offsets_last.value_at(point));
+        };
+    }
+
+    #[test]
+    #[should_panic(expected = "positive octave count")]
+    fn no_fractional_octaves() {
+        SimplexNode::<2>::new(1).fbm(3, 0.5, 2.0).with_fractional_octaves(0.0);
+    }
+
+    #[test]
+    fn periodic_octave_count() {
+        let node = PerlinNode::<2>::new(1).fbm(1, 0.5, 2.0).with_fractional_octaves(3.5).with_period([3, 4]);
+
+        for idx in 0..100 {
+            let point = RealPoint::<2>::new([(idx as f64) * 0.37 - 20.0, (idx as f64) * 0.11]);
+
+            assert!((node.value_at(point) - node.value_at(point + RealPoint::<2>::new([3.0, -8.0]))).abs() < 1e-9);
+        };
+    }
+
+    #[test]
+    #[should_panic(expected = "set the octave count before the period")]
+    fn octaves_after_period() {
+        PerlinNode::<2>::new(1).fbm(3, 0.5, 2.0).with_period([3, 4]).with_fractional_octaves(3.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't rotate their octaves")]
+    fn rotation_after_period() {
+        PerlinNode::<2>::new(1).fbm(3, 0.5, 2.0).with_period([3, 4]).with_octave_rotation(LinearMap::<2>::rotation(0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't rotate their octaves")]
+    fn rotation_before_period() {
+        PerlinNode::<2>::new(1).fbm(3, 0.5, 2.0).with_octave_rotation(LinearMap::<2>::rotation(0.5)).with_period([3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "lacunarity of at least 1.0")]
+    fn periodic_zero_lacunarity() {
+        PerlinNode::<2>::new(1).fbm(3, 0.5, 0.0).with_period([3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow a u32 period at octave 12")]
+    fn periodic_overflow() {
+        PerlinNode::<2>::new(1).fbm(13, 0.5, 4.0).with_period([512, 512]);
+    }
 }
@@ -1,19 +1,26 @@
 use std::marker::PhantomData;
 
 use crate::geometry::{DistanceMetric, RealPoint};
-use crate::utils;
-use super::NoiseNode;
+use super::{NoiseNode, PatternProfile};
 
+#[derive(Clone)]
 pub struct HypersphereNode<const DIM: usize, Metric>
 where Metric: DistanceMetric {
     frequency: f64,
+    profile: PatternProfile,
     phantom: PhantomData<Metric>
 }
 
 impl<const DIM: usize, Metric> HypersphereNode<DIM, Metric>
 where Metric: DistanceMetric {
     pub fn new(frequency: f64) -> Self {
-        Self { frequency, phantom: PhantomData }
+        Self { frequency, profile: PatternProfile::Sigmoid(-1.2), phantom: PhantomData }
+    }
+
+    // Shapes each shell, from 0.0 halfway between shells to 1.0 on one
+    pub fn with_profile(mut self, profile: PatternProfile) -> Self {
+        self.profile = profile;
+        self
     }
 }
 
@@ -26,6 +33,27 @@ where Metric: DistanceMetric {
         let outer_distance = 1.0 - inner_distance;
         let nearest_distance = inner_distance.min(outer_distance);
 
-        utils::sigmoid(-1.2, nearest_distance.mul_add(-2.0, 1.0))
+        self.profile.apply(nearest_distance.mul_add(-2.0, 1.0))
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{EuclideanMetric, RealPoint};
+    use crate::noise::NoiseNode;
+    use crate::utils;
+    use super::HypersphereNode;
+
+    #[test]
+    fn default_profile() {
+        let hyperspheres = HypersphereNode::<2, EuclideanMetric>::new(1.0);
+
+        // 0.875 from the origin is 0.125 from the next shell, a quarter of the way from halfway
+        // between shells to on one, which the default profile eases as it always has
+        assert_eq!(hyperspheres.value_at(RealPoint::<2>::new([0.875, 0.0])), utils::sigmoid(-1.2, 0.75));
+        assert_eq!(hyperspheres.value_at(RealPoint::<2>::new([0.0, 2.0])), utils::sigmoid(-1.2, 1.0));
     }
 }
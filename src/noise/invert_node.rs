@@ -1,21 +1,28 @@
 use crate::geometry::RealPoint;
 use super::NoiseNode;
 
-pub struct InvertNode<'a, const DIM: usize, Source>
+#[derive(Clone)]
+pub struct InvertNode<const DIM: usize, Source>
 where Source: NoiseNode<DIM> {
-    source: &'a Source
+    source: Source
 }
 
-impl<'a, const DIM: usize, Source> InvertNode<'a, DIM, Source>
+impl<const DIM: usize, Source> InvertNode<DIM, Source>
 where Source: NoiseNode<DIM> {
-    pub fn new(source: &'a Source) -> Self {
+    pub fn new(source: Source) -> Self {
         Self { source }
     }
 }
 
-impl<'a, const DIM: usize, Source> NoiseNode<DIM> for InvertNode<'a, DIM, Source>
+impl<const DIM: usize, Source> NoiseNode<DIM> for InvertNode<DIM, Source>
 where Source: NoiseNode<DIM> {
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
         1.0 - self.source.value_at(point)
     }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (value, gradient) = self.source.value_and_gradient(point);
+
+        (1.0 - value, gradient * -1.0)
+    }
 }
@@ -2,19 +2,20 @@ use crate::geometry::RealPoint;
 use crate::utils;
 use super::NoiseNode;
 
-pub struct KneadNode<'a, const DIM: usize, Source>
+#[derive(Clone)]
+pub struct KneadNode<const DIM: usize, Source>
 where Source: NoiseNode<DIM> {
-    source: &'a Source
+    source: Source
 }
 
-impl<'a, const DIM: usize, Source> KneadNode<'a, DIM, Source>
+impl<const DIM: usize, Source> KneadNode<DIM, Source>
 where Source: NoiseNode<DIM> {
-    pub fn new(source: &'a Source) -> Self {
+    pub fn new(source: Source) -> Self {
         Self { source }
     }
 }
 
-impl<'a, const DIM: usize, Source> NoiseNode<DIM> for KneadNode<'a, DIM, Source>
+impl<const DIM: usize, Source> NoiseNode<DIM> for KneadNode<DIM, Source>
 where Source: NoiseNode<DIM> {
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
         utils::unit_to_neg_unit(self.source.value_at(point)).abs()
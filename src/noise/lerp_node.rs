@@ -2,22 +2,23 @@ use crate::geometry::RealPoint;
 use crate::utils;
 use super::NoiseNode;
 
-pub struct LerpNode<'a, const DIM: usize, Bias, Lhs, Rhs>
+#[derive(Clone)]
+pub struct LerpNode<const DIM: usize, Bias, Lhs, Rhs>
 where Bias: NoiseNode<DIM>, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
-    bias: &'a Bias,
-    lhs: &'a Lhs,
-    rhs: &'a Rhs
+    bias: Bias,
+    lhs: Lhs,
+    rhs: Rhs
 }
 
 
-impl<'a, const DIM: usize, Bias, Lhs, Rhs> LerpNode<'a, DIM, Bias, Lhs, Rhs>
+impl<const DIM: usize, Bias, Lhs, Rhs> LerpNode<DIM, Bias, Lhs, Rhs>
 where Bias: NoiseNode<DIM>, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
-    pub fn new(bias: &'a Bias, lhs: &'a Lhs, rhs: &'a Rhs) -> Self {
+    pub fn new(bias: Bias, lhs: Lhs, rhs: Rhs) -> Self {
         Self { bias, lhs, rhs }
     }
 }
 
-impl<'a, const DIM: usize, Bias, Lhs, Rhs> NoiseNode<DIM> for LerpNode<'a, DIM, Bias, Lhs, Rhs>
+impl<const DIM: usize, Bias, Lhs, Rhs> NoiseNode<DIM> for LerpNode<DIM, Bias, Lhs, Rhs>
 where Bias: NoiseNode<DIM>, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
         let bias = self.bias.value_at(point);
@@ -26,4 +27,14 @@ where Bias: NoiseNode<DIM>, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
 
         utils::lerp(bias, lhs, rhs)
     }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (bias, bias_gradient) = self.bias.value_and_gradient(point);
+        let (lhs, lhs_gradient) = self.lhs.value_and_gradient(point);
+        let (rhs, rhs_gradient) = self.rhs.value_and_gradient(point);
+
+        let gradient = lhs_gradient + (rhs_gradient - lhs_gradient) * bias + bias_gradient * (rhs - lhs);
+
+        (utils::lerp(bias, lhs, rhs), gradient)
+    }
 }
@@ -0,0 +1,56 @@
+use crate::geometry::RealPoint;
+use super::{NoiseNode, PatternProfile};
+
+// Ramps from 0.0 at `start` to 1.0 at `end`, constant across the line between them and clamped
+// beyond either end
+#[derive(Clone)]
+pub struct LinearGradientNode<const DIM: usize> {
+    start: RealPoint<DIM>,
+    direction: RealPoint<DIM>,
+    profile: PatternProfile
+}
+
+impl<const DIM: usize> LinearGradientNode<DIM> {
+    pub fn new(start: RealPoint<DIM>, end: RealPoint<DIM>) -> Self {
+        let span = end - start;
+        let squared_length = span.dot_product(span);
+
+        assert!(squared_length > 0.0, "gradients need distinct start and end points");
+
+        // Scaled so the projection of `end` lands on 1.0
+        Self { start, direction: span / squared_length, profile: PatternProfile::Linear }
+    }
+
+    pub fn with_profile(mut self, profile: PatternProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+}
+
+impl<const DIM: usize> NoiseNode<DIM> for LinearGradientNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let progress = (point - self.start).dot_product(self.direction);
+
+        self.profile.apply(progress.clamp(0.0, 1.0))
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::NoiseNode;
+    use super::LinearGradientNode;
+
+    #[test]
+    fn ramp() {
+        let at = |x: f64, y: f64| RealPoint::<2>::new([x, y]);
+        let linear = LinearGradientNode::new(at(1.0, 0.0), at(3.0, 0.0));
+
+        assert_eq!(linear.value_at(at(0.0, 5.0)), 0.0);
+        assert_eq!(linear.value_at(at(2.5, -5.0)), 0.75);
+        assert_eq!(linear.value_at(at(4.0, 0.0)), 1.0);
+    }
+}
@@ -0,0 +1,26 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+#[derive(Clone)]
+pub struct MinNode<const DIM: usize, Lhs, Rhs>
+where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
+    lhs: Lhs,
+    rhs: Rhs
+}
+
+impl<const DIM: usize, Lhs, Rhs> MinNode<DIM, Lhs, Rhs>
+where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
+    pub fn new(lhs: Lhs, rhs: Rhs) -> Self {
+        Self { lhs, rhs }
+    }
+}
+
+impl<const DIM: usize, Lhs, Rhs> NoiseNode<DIM> for MinNode<DIM, Lhs, Rhs>
+where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let lhs_value = self.lhs.value_at(point);
+        let rhs_value = self.rhs.value_at(point);
+
+        lhs_value.min(rhs_value)
+    }
+}
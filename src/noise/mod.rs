@@ -1,39 +1,312 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::geometry::RealPoint;
+use crate::utils;
+
 mod function;
 
+pub mod derivative;
+pub mod grid;
+
+#[cfg(feature = "parallel")]
+pub mod parallel;
+
+mod abs_node;
+mod add_node;
+mod blend_node;
+mod cache_node;
+mod checkerboard_node;
+mod circle_node;
+mod clamp_node;
 mod const_node;
+mod curl_node;
+mod curve_node;
+mod cylinders_node;
+mod divide_node;
+mod extrude_node;
+mod gabor_node;
+mod grid_cache_node;
 mod harmonic_node;
 mod hypersphere_node;
 mod invert_node;
 mod knead_node;
 mod lerp_node;
+mod linear_gradient_node;
+mod max_node;
+mod min_node;
 mod multiply_node;
+mod node_ops;
+mod noise_node_ext;
 mod overlay_node;
+mod pattern_profile;
 mod perlin_node;
+mod pow_node;
+mod radial_gradient_node;
+mod scale_bias_node;
 mod screen_node;
+mod select_node;
 mod sigmoid_node;
+mod simplex_node;
+mod slice_node;
 mod soft_light_node;
+mod sphere_node;
+mod spheres_node;
 mod static_node;
+mod stripes_node;
+mod subtract_node;
+mod terrace_node;
 mod tile_node;
+mod torus_node;
 mod transform_node;
+mod value_node;
+mod warp_node;
 mod worley_node;
 
+pub use abs_node::AbsNode;
+pub use add_node::AddNode;
+pub use blend_node::{BlendMode, BlendNode};
+pub use cache_node::CacheNode;
+pub use checkerboard_node::CheckerboardNode;
+pub use circle_node::CircleNode;
+pub use clamp_node::ClampNode;
 pub use const_node::ConstNode;
-pub use harmonic_node::HarmonicNode;
+pub use curl_node::CurlNode;
+pub use curve_node::{CurveInterpolation, CurveNode};
+pub use cylinders_node::CylindersNode;
+pub use divide_node::DivideNode;
+pub use extrude_node::ExtrudeNode;
+pub use gabor_node::{GaborNode, GaborOrientation};
+pub use grid_cache_node::GridCacheNode;
+pub use harmonic_node::{FractalKind, HarmonicNode};
 pub use hypersphere_node::HypersphereNode;
 pub use invert_node::InvertNode;
 pub use knead_node::KneadNode;
 pub use lerp_node::LerpNode;
+pub use linear_gradient_node::LinearGradientNode;
+pub use max_node::MaxNode;
+pub use min_node::MinNode;
 pub use multiply_node::MultiplyNode;
+pub use noise_node_ext::NoiseNodeExt;
 pub use overlay_node::OverlayNode;
+pub use pattern_profile::PatternProfile;
 pub use perlin_node::PerlinNode;
+pub use pow_node::PowNode;
+pub use radial_gradient_node::RadialGradientNode;
+pub use scale_bias_node::ScaleBiasNode;
 pub use screen_node::ScreenNode;
+pub use select_node::SelectNode;
 pub use sigmoid_node::SigmoidNode;
+pub use simplex_node::SimplexNode;
+pub use slice_node::SliceNode;
 pub use soft_light_node::SoftLightNode;
+pub use sphere_node::{CubeFace, SphereMapping, SphereNode};
+pub use spheres_node::SpheresNode;
 pub use static_node::StaticNode;
+pub use stripes_node::{StripesNode, Waveform};
+pub use subtract_node::SubtractNode;
+pub use terrace_node::TerraceNode;
 pub use tile_node::TileNode;
+pub use torus_node::TorusNode;
 pub use transform_node::TransformNode;
-pub use worley_node::{WorleyNode, WorleyPaintMethod};
+pub use value_node::{ValueInterpolation, ValueNode};
+pub use warp_node::WarpNode;
+pub use worley_node::{WorleyNode, WorleyPaintMethod, WorleyPointCount};
 
 pub trait NoiseNode<const DIM: usize> {
-    fn value_at(&self, point: crate::geometry::RealPoint<DIM>) -> f64;
+    fn value_at(&self, point: RealPoint<DIM>) -> f64;
+
+    // Samples `origin + index * step` for every index below `extent`, laid out as described in
+    // `grid`
+    fn fill_grid(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, extent: [usize; DIM], output: &mut [f64]) {
+        self.fill_grid_section(origin, step, [0; DIM], extent, output)
+    }
+
+    // Samples the indices `start..(start + extent)` of the same grid, giving bit-identical values
+    // to the matching part of `fill_grid`. Nodes with lattice structure override this to share
+    // work between nearby points
+    fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        grid::fill_per_point(self, origin, step, start, extent, output)
+    }
+
+    // Value at `point` along with its partial derivatives along every axis. Nodes built from
+    // differentiable functions override this analytically, the rest fall back to central
+    // differences
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        derivative::central_difference(self, point)
+    }
+}
+
+// Nodes that can tile seamlessly, repeating after `period` units along every axis
+pub trait PeriodicNode<const DIM: usize>: NoiseNode<DIM> {
+    fn with_period(self, period: [u32; DIM]) -> Self;
+}
+
+// Fields that assign a vector, rather than a single value, to every point
+pub trait VectorNoiseNode<const DIM: usize> {
+    fn vector_at(&self, point: RealPoint<DIM>) -> RealPoint<DIM>;
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Forwarding impls, so graphs can borrow, own or share their sources
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for &Source
+where Source: NoiseNode<DIM> + ?Sized {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        (**self).value_at(point)
+    }
+
+    fn fill_grid(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, extent: [usize; DIM], output: &mut [f64]) {
+        (**self).fill_grid(origin, step, extent, output)
+    }
+
+    fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        (**self).fill_grid_section(origin, step, start, extent, output)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        (**self).value_and_gradient(point)
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for Box<Source>
+where Source: NoiseNode<DIM> + ?Sized {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        (**self).value_at(point)
+    }
+
+    fn fill_grid(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, extent: [usize; DIM], output: &mut [f64]) {
+        (**self).fill_grid(origin, step, extent, output)
+    }
+
+    fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        (**self).fill_grid_section(origin, step, start, extent, output)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        (**self).value_and_gradient(point)
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for Rc<Source>
+where Source: NoiseNode<DIM> + ?Sized {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        (**self).value_at(point)
+    }
+
+    fn fill_grid(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, extent: [usize; DIM], output: &mut [f64]) {
+        (**self).fill_grid(origin, step, extent, output)
+    }
+
+    fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        (**self).fill_grid_section(origin, step, start, extent, output)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        (**self).value_and_gradient(point)
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for Arc<Source>
+where Source: NoiseNode<DIM> + ?Sized {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        (**self).value_at(point)
+    }
+
+    fn fill_grid(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, extent: [usize; DIM], output: &mut [f64]) {
+        (**self).fill_grid(origin, step, extent, output)
+    }
+
+    fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        (**self).fill_grid_section(origin, step, start, extent, output)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        (**self).value_and_gradient(point)
+    }
+}
+
+// An array of scalar fields acts as a vector field, with each [0.0, 1.0] value giving a component
+// in [-1.0, 1.0]
+impl<const DIM: usize, Node> VectorNoiseNode<DIM> for [Node; DIM]
+where Node: NoiseNode<DIM> {
+    fn vector_at(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        let mut vector = RealPoint::<DIM>::origin();
+
+        for (coord, node) in vector.iter_mut().zip(self.iter()) {
+            *coord = utils::unit_to_neg_unit(node.value_at(point));
+        };
+
+        vector
+    }
+}
+
+impl<const DIM: usize, Source> VectorNoiseNode<DIM> for &Source
+where Source: VectorNoiseNode<DIM> + ?Sized {
+    fn vector_at(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        (**self).vector_at(point)
+    }
+}
+
+impl<const DIM: usize, Source> VectorNoiseNode<DIM> for Box<Source>
+where Source: VectorNoiseNode<DIM> + ?Sized {
+    fn vector_at(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        (**self).vector_at(point)
+    }
+}
+
+impl<const DIM: usize, Source> VectorNoiseNode<DIM> for Rc<Source>
+where Source: VectorNoiseNode<DIM> + ?Sized {
+    fn vector_at(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        (**self).vector_at(point)
+    }
+}
+
+impl<const DIM: usize, Source> VectorNoiseNode<DIM> for Arc<Source>
+where Source: VectorNoiseNode<DIM> + ?Sized {
+    fn vector_at(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        (**self).vector_at(point)
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{EuclideanMetric, RealPoint};
+    use super::{
+        CircleNode, NoiseNode, NoiseNodeExt, PeriodicNode, PerlinNode, SimplexNode, TorusNode, WorleyNode,
+        WorleyPaintMethod
+    };
+
+    fn assert_periodic<const DIM: usize>(node: &impl NoiseNode<DIM>, period: [u32; DIM]) {
+        for idx in 0..100 {
+            let t = idx as f64;
+
+            let mut point = RealPoint::<DIM>::origin();
+            let mut wrapped_point = point;
+
+            for (dim, (coord, wrapped_coord)) in point.iter_mut().zip(wrapped_point.iter_mut()).enumerate() {
+                *coord = t * (0.37 + 0.11 * (dim as f64)) - 20.0;
+                *wrapped_coord = *coord + (period[dim] as f64) * ((idx % 3) as f64);
+            };
+
+            assert!((node.value_at(point) - node.value_at(wrapped_point)).abs() < 1e-9);
+        };
+    }
+
+    #[test]
+    fn periodic_nodes() {
+        assert_periodic(&PerlinNode::<1>::new(1).with_period([3]), [3]);
+        assert_periodic(&PerlinNode::<2>::new(1).with_period([4, 5]), [4, 5]);
+        assert_periodic(&PerlinNode::<3>::new(1).with_period([2, 3, 4]), [2, 3, 4]);
+        assert_periodic(&PerlinNode::<4>::new(1).with_period([2, 3, 4, 5]), [2, 3, 4, 5]);
+
+        assert_periodic(&WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::F2MinusF1).with_period([3, 4]), [3, 4]);
+        assert_periodic(&PerlinNode::<2>::new(1).fbm(4, 0.5, 2.0).with_octave_offsets(1).with_period([4, 5]), [4, 5]);
+
+        assert_periodic(&CircleNode::new(SimplexNode::<2>::new(1), 3.0), [3]);
+        assert_periodic(&TorusNode::new(SimplexNode::<4>::new(1), [4.0, 5.0]), [4, 5]);
+    }
 }
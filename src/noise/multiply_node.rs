@@ -1,25 +1,48 @@
 use crate::geometry::RealPoint;
 use super::NoiseNode;
+use super::blend_node::apply_opacity;
 
-pub struct MultiplyNode<'a, const DIM: usize, Lhs, Rhs>
+#[derive(Clone)]
+pub struct MultiplyNode<const DIM: usize, Lhs, Rhs>
 where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
-    lhs: &'a Lhs,
-    rhs: &'a Rhs
+    lhs: Lhs,
+    rhs: Rhs,
+    opacity: f64
 }
 
-impl<'a, const DIM: usize, Lhs, Rhs> MultiplyNode<'a, DIM, Lhs, Rhs>
+impl<const DIM: usize, Lhs, Rhs> MultiplyNode<DIM, Lhs, Rhs>
 where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
-    pub fn new(lhs: &'a Lhs, rhs: &'a Rhs) -> Self {
-        Self { lhs, rhs }
+    pub fn new(lhs: Lhs, rhs: Rhs) -> Self {
+        Self { lhs, rhs, opacity: 1.0 }
+    }
+
+    // Fades the product back towards `lhs`, as a multiply layer would in an image editor
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity;
+        self
     }
 }
 
-impl<'a, const DIM: usize, Lhs, Rhs> NoiseNode<DIM> for MultiplyNode<'a, DIM, Lhs, Rhs>
+impl<const DIM: usize, Lhs, Rhs> NoiseNode<DIM> for MultiplyNode<DIM, Lhs, Rhs>
 where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
         let lhs_value = self.lhs.value_at(point);
         let rhs_value = self.rhs.value_at(point);
 
-        lhs_value * rhs_value
+        apply_opacity(self.opacity, lhs_value, lhs_value * rhs_value)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (lhs_value, lhs_gradient) = self.lhs.value_and_gradient(point);
+        let (rhs_value, rhs_gradient) = self.rhs.value_and_gradient(point);
+
+        let value = apply_opacity(self.opacity, lhs_value, lhs_value * rhs_value);
+        let gradient = lhs_gradient * rhs_value + rhs_gradient * lhs_value;
+
+        if self.opacity == 1.0 {
+            return (value, gradient);
+        };
+
+        (value, lhs_gradient + (gradient - lhs_gradient) * self.opacity)
     }
 }
@@ -0,0 +1,243 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::geometry::DistanceMetric;
+use super::*;
+
+// Arithmetic between nodes builds the matching combinator, and arithmetic with an `f64` folds
+// into a `ScaleBiasNode`. Trait coherence rules out a blanket impl over every `NoiseNode`, so
+// each node type opts in through `impl_node_ops!` below, with the dimension given up front for
+// nodes that only exist in one. `ConstNode` is left out, since it has no dimension of its own to
+// build the output type from; use a bare `f64` instead
+macro_rules! impl_node_ops {
+    ([$($generics:tt)*] $node:ty) => {
+        impl_node_ops!(DIM; [$($generics)*] $node);
+    };
+
+    ($dim:tt; [$($generics:tt)*] $node:ty) => {
+        impl<$($generics)*, Other> Add<Other> for $node
+        where Other: NoiseNode<$dim> {
+            type Output = AddNode<$dim, Self, Other>;
+
+            fn add(self, other: Other) -> Self::Output {
+                AddNode::new(self, other)
+            }
+        }
+
+        impl<$($generics)*, Other> Sub<Other> for $node
+        where Other: NoiseNode<$dim> {
+            type Output = SubtractNode<$dim, Self, Other>;
+
+            fn sub(self, other: Other) -> Self::Output {
+                SubtractNode::new(self, other)
+            }
+        }
+
+        impl<$($generics)*, Other> Mul<Other> for $node
+        where Other: NoiseNode<$dim> {
+            type Output = MultiplyNode<$dim, Self, Other>;
+
+            fn mul(self, other: Other) -> Self::Output {
+                MultiplyNode::new(self, other)
+            }
+        }
+
+        impl<$($generics)*, Other> Div<Other> for $node
+        where Other: NoiseNode<$dim> {
+            type Output = DivideNode<$dim, Self, Other>;
+
+            fn div(self, other: Other) -> Self::Output {
+                DivideNode::new(self, other)
+            }
+        }
+
+        impl<$($generics)*> Add<f64> for $node {
+            type Output = ScaleBiasNode<$dim, Self>;
+
+            fn add(self, scalar: f64) -> Self::Output {
+                ScaleBiasNode::new(self, 1.0, scalar)
+            }
+        }
+
+        impl<$($generics)*> Sub<f64> for $node {
+            type Output = ScaleBiasNode<$dim, Self>;
+
+            fn sub(self, scalar: f64) -> Self::Output {
+                ScaleBiasNode::new(self, 1.0, -scalar)
+            }
+        }
+
+        impl<$($generics)*> Mul<f64> for $node {
+            type Output = ScaleBiasNode<$dim, Self>;
+
+            fn mul(self, scalar: f64) -> Self::Output {
+                ScaleBiasNode::new(self, scalar, 0.0)
+            }
+        }
+
+        impl<$($generics)*> Div<f64> for $node {
+            type Output = ScaleBiasNode<$dim, Self>;
+
+            fn div(self, scalar: f64) -> Self::Output {
+                ScaleBiasNode::new(self, scalar.recip(), 0.0)
+            }
+        }
+
+        impl<$($generics)*> Add<$node> for f64 {
+            type Output = ScaleBiasNode<$dim, $node>;
+
+            fn add(self, node: $node) -> Self::Output {
+                ScaleBiasNode::new(node, 1.0, self)
+            }
+        }
+
+        impl<$($generics)*> Sub<$node> for f64 {
+            type Output = ScaleBiasNode<$dim, $node>;
+
+            fn sub(self, node: $node) -> Self::Output {
+                ScaleBiasNode::new(node, -1.0, self)
+            }
+        }
+
+        impl<$($generics)*> Mul<$node> for f64 {
+            type Output = ScaleBiasNode<$dim, $node>;
+
+            fn mul(self, node: $node) -> Self::Output {
+                ScaleBiasNode::new(node, self, 0.0)
+            }
+        }
+
+        impl<$($generics)*> Div<$node> for f64 {
+            type Output = DivideNode<$dim, ConstNode, $node>;
+
+            fn div(self, node: $node) -> Self::Output {
+                DivideNode::new(ConstNode::new(self), node)
+            }
+        }
+
+        impl<$($generics)*> Neg for $node {
+            type Output = ScaleBiasNode<$dim, Self>;
+
+            fn neg(self) -> Self::Output {
+                ScaleBiasNode::new(self, -1.0, 0.0)
+            }
+        }
+    };
+}
+
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] AbsNode<DIM, Source>);
+impl_node_ops!([const DIM: usize, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] AddNode<DIM, Lhs, Rhs>);
+impl_node_ops!([const DIM: usize, Base: NoiseNode<DIM>, Layer: NoiseNode<DIM>] BlendNode<DIM, Base, Layer>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] CacheNode<DIM, Source>);
+impl_node_ops!([const DIM: usize] CheckerboardNode<DIM>);
+impl_node_ops!(1; [Source: NoiseNode<2>] CircleNode<Source>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] ClampNode<DIM, Source>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] CurveNode<DIM, Source>);
+impl_node_ops!([const DIM: usize, Metric: DistanceMetric] CylindersNode<DIM, Metric>);
+impl_node_ops!([const DIM: usize, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] DivideNode<DIM, Lhs, Rhs>);
+impl_node_ops!([const DIM: usize, const SOURCE_DIM: usize, Source: NoiseNode<SOURCE_DIM>] ExtrudeNode<DIM, SOURCE_DIM, Source>);
+impl_node_ops!([const DIM: usize, Field: VectorNoiseNode<DIM>] GaborNode<DIM, Field>);
+impl_node_ops!([const DIM: usize] GridCacheNode<DIM>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] HarmonicNode<DIM, Source>);
+impl_node_ops!([const DIM: usize, Metric: DistanceMetric] HypersphereNode<DIM, Metric>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] InvertNode<DIM, Source>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] KneadNode<DIM, Source>);
+impl_node_ops!([const DIM: usize, Bias: NoiseNode<DIM>, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] LerpNode<DIM, Bias, Lhs, Rhs>);
+impl_node_ops!([const DIM: usize] LinearGradientNode<DIM>);
+impl_node_ops!([const DIM: usize, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] MaxNode<DIM, Lhs, Rhs>);
+impl_node_ops!([const DIM: usize, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] MinNode<DIM, Lhs, Rhs>);
+impl_node_ops!([const DIM: usize, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] MultiplyNode<DIM, Lhs, Rhs>);
+impl_node_ops!([const DIM: usize, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] OverlayNode<DIM, Lhs, Rhs>);
+impl_node_ops!([const DIM: usize] PerlinNode<DIM>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] PowNode<DIM, Source>);
+impl_node_ops!([const DIM: usize, Metric: DistanceMetric] RadialGradientNode<DIM, Metric>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] ScaleBiasNode<DIM, Source>);
+impl_node_ops!([const DIM: usize, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] ScreenNode<DIM, Lhs, Rhs>);
+impl_node_ops!([const DIM: usize, Outside: NoiseNode<DIM>, Inside: NoiseNode<DIM>, Control: NoiseNode<DIM>] SelectNode<DIM, Outside, Inside, Control>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] SigmoidNode<DIM, Source>);
+impl_node_ops!([const DIM: usize] SimplexNode<DIM>);
+impl_node_ops!([const DIM: usize, const SOURCE_DIM: usize, Source: NoiseNode<SOURCE_DIM>] SliceNode<DIM, SOURCE_DIM, Source>);
+impl_node_ops!([const DIM: usize, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] SoftLightNode<DIM, Lhs, Rhs>);
+impl_node_ops!(2; [Source: NoiseNode<3>] SphereNode<Source>);
+impl_node_ops!([const DIM: usize, Metric: DistanceMetric] SpheresNode<DIM, Metric>);
+impl_node_ops!([const DIM: usize] StaticNode<DIM>);
+impl_node_ops!([const DIM: usize] StripesNode<DIM>);
+impl_node_ops!([const DIM: usize, Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM>] SubtractNode<DIM, Lhs, Rhs>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] TerraceNode<DIM, Source>);
+impl_node_ops!([const DIM: usize] TileNode<DIM>);
+impl_node_ops!(2; [Source: NoiseNode<4>] TorusNode<Source>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>] TransformNode<DIM, Source>);
+impl_node_ops!([const DIM: usize] ValueNode<DIM>);
+impl_node_ops!([const DIM: usize, Source: NoiseNode<DIM>, Displacement: VectorNoiseNode<DIM>] WarpNode<DIM, Source, Displacement>);
+impl_node_ops!([const DIM: usize, Metric: DistanceMetric] WorleyNode<DIM, Metric>);
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::{ConstNode, NoiseNode, NoiseNodeExt, PerlinNode, StaticNode};
+
+    #[test]
+    fn node_arithmetic() {
+        let point = RealPoint::<2>::new([1.25, -3.5]);
+
+        let lhs = PerlinNode::<2>::new(1);
+        let rhs = StaticNode::<2>::new(2, 0.0, 1.0);
+
+        let lhs_value = lhs.value_at(point);
+        let rhs_value = rhs.value_at(point);
+
+        assert_eq!((PerlinNode::<2>::new(1) + StaticNode::<2>::new(2, 0.0, 1.0)).value_at(point), lhs_value + rhs_value);
+        assert_eq!((PerlinNode::<2>::new(1) - StaticNode::<2>::new(2, 0.0, 1.0)).value_at(point), lhs_value - rhs_value);
+        assert_eq!((PerlinNode::<2>::new(1) * StaticNode::<2>::new(2, 0.0, 1.0)).value_at(point), lhs_value * rhs_value);
+        assert_eq!((PerlinNode::<2>::new(1) / StaticNode::<2>::new(2, 0.0, 1.0)).value_at(point), lhs_value / rhs_value);
+    }
+
+    #[test]
+    fn scalar_arithmetic() {
+        let point = RealPoint::<3>::new([0.5, 7.25, -2.0]);
+        let value = PerlinNode::<3>::new(1).value_at(point);
+
+        assert_eq!((PerlinNode::<3>::new(1) * 2.0 + 0.5).value_at(point), value * 2.0 + 0.5);
+        assert_eq!((1.0 - PerlinNode::<3>::new(1)).value_at(point), 1.0 - value);
+        assert_eq!((-PerlinNode::<3>::new(1)).value_at(point), -value);
+        assert_eq!((2.0 / PerlinNode::<3>::new(1)).value_at(point), 2.0 / value);
+    }
+
+    #[test]
+    fn node_functions() {
+        let point = RealPoint::<2>::new([1.25, -3.5]);
+
+        let lhs = PerlinNode::<2>::new(1);
+        let rhs = StaticNode::<2>::new(2, 0.0, 1.0);
+
+        let lhs_value = lhs.value_at(point);
+        let rhs_value = rhs.value_at(point);
+
+        assert_eq!((&lhs).min(&rhs).value_at(point), lhs_value.min(rhs_value));
+        assert_eq!((&lhs).max(&rhs).value_at(point), lhs_value.max(rhs_value));
+        assert_eq!((&lhs).clamp(0.45, 0.55).value_at(point), lhs_value.clamp(0.45, 0.55));
+        assert_eq!((PerlinNode::<2>::new(1) - 0.5).abs().value_at(point), (lhs_value - 0.5).abs());
+        assert_eq!((&lhs).pow(2.5).value_at(point), lhs_value.powf(2.5));
+
+        assert_eq!(ConstNode::new(0.3).min(ConstNode::new(0.7)).value_at(point), 0.3);
+        assert_eq!(ConstNode::new(0.3).max(ConstNode::new(0.7)).value_at(point), 0.7);
+        assert_eq!(ConstNode::new(0.9).clamp(0.0, 0.5).value_at(point), 0.5);
+        assert_eq!(ConstNode::new(-0.1).clamp(0.0, 0.5).value_at(point), 0.0);
+        assert_eq!(ConstNode::new(-0.25).abs().value_at(point), 0.25);
+        assert_eq!(ConstNode::new(0.25).pow(0.5).value_at(point), 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "clamps need min <= max")]
+    fn inverted_clamp() {
+        PerlinNode::<2>::new(1).clamp(0.6, 0.4);
+    }
+
+    #[test]
+    #[should_panic(expected = "clamps need min <= max")]
+    fn nan_clamp() {
+        PerlinNode::<2>::new(1).clamp(f64::NAN, 1.0);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::geometry::AffineMap;
+use super::{
+    AbsNode, BlendMode, BlendNode, CacheNode, ClampNode, CurveInterpolation, CurveNode,
+    HarmonicNode, InvertNode, KneadNode, LerpNode, MaxNode, MinNode, MultiplyNode, NoiseNode,
+    OverlayNode, PowNode, ScaleBiasNode, ScreenNode, SelectNode, SigmoidNode, SoftLightNode,
+    TerraceNode, TransformNode, VectorNoiseNode, WarpNode
+};
+
+// Chainable constructors for the combinator nodes, so graphs read from source to output
+pub trait NoiseNodeExt<const DIM: usize>: NoiseNode<DIM> + Sized {
+    fn fbm(self, num_octaves: usize, persistence: f64, lacunarity: f64) -> HarmonicNode<DIM, Self> {
+        HarmonicNode::new(self, num_octaves, persistence, lacunarity)
+    }
+
+    fn invert(self) -> InvertNode<DIM, Self> {
+        InvertNode::new(self)
+    }
+
+    fn knead(self) -> KneadNode<DIM, Self> {
+        KneadNode::new(self)
+    }
+
+    fn sigmoid(self, beta: f64) -> SigmoidNode<DIM, Self> {
+        SigmoidNode::new(self, beta)
+    }
+
+    fn curve(self, control_points: Vec<(f64, f64)>, interpolation: CurveInterpolation) -> CurveNode<DIM, Self> {
+        CurveNode::new(self, control_points, interpolation)
+    }
+
+    fn terrace(self, num_steps: usize) -> TerraceNode<DIM, Self> {
+        TerraceNode::new(self, num_steps)
+    }
+
+    fn transform<Map: Into<AffineMap<DIM>>>(self, map: Map) -> TransformNode<DIM, Self> {
+        TransformNode::new(self, map)
+    }
+
+    fn warp<Displacement>(self, displacement: Displacement, strength: f64) -> WarpNode<DIM, Self, Displacement>
+    where Displacement: VectorNoiseNode<DIM> {
+        WarpNode::new(self, displacement, strength)
+    }
+
+    fn lerp<Rhs, Bias>(self, rhs: Rhs, bias: Bias) -> LerpNode<DIM, Bias, Self, Rhs>
+    where Rhs: NoiseNode<DIM>, Bias: NoiseNode<DIM> {
+        LerpNode::new(bias, self, rhs)
+    }
+
+    // Shows `inside` wherever `control` lies within [lower, upper], and `self` everywhere else
+    fn select<Inside, Control>(self, inside: Inside, control: Control, lower: f64, upper: f64) -> SelectNode<DIM, Self, Inside, Control>
+    where Inside: NoiseNode<DIM>, Control: NoiseNode<DIM> {
+        SelectNode::new(self, inside, control, lower, upper)
+    }
+
+    fn multiply<Rhs>(self, rhs: Rhs) -> MultiplyNode<DIM, Self, Rhs>
+    where Rhs: NoiseNode<DIM> {
+        MultiplyNode::new(self, rhs)
+    }
+
+    fn screen<Rhs>(self, rhs: Rhs) -> ScreenNode<DIM, Self, Rhs>
+    where Rhs: NoiseNode<DIM> {
+        ScreenNode::new(self, rhs)
+    }
+
+    fn overlay<Rhs>(self, rhs: Rhs) -> OverlayNode<DIM, Self, Rhs>
+    where Rhs: NoiseNode<DIM> {
+        OverlayNode::new(self, rhs)
+    }
+
+    fn soft_light<Rhs>(self, rhs: Rhs) -> SoftLightNode<DIM, Self, Rhs>
+    where Rhs: NoiseNode<DIM> {
+        SoftLightNode::new(self, rhs)
+    }
+
+    fn blend<Layer>(self, layer: Layer, mode: BlendMode) -> BlendNode<DIM, Self, Layer>
+    where Layer: NoiseNode<DIM> {
+        BlendNode::new(self, layer, mode)
+    }
+
+    fn scale_bias(self, scale: f64, bias: f64) -> ScaleBiasNode<DIM, Self> {
+        ScaleBiasNode::new(self, scale, bias)
+    }
+
+    fn abs(self) -> AbsNode<DIM, Self> {
+        AbsNode::new(self)
+    }
+
+    fn pow(self, exponent: f64) -> PowNode<DIM, Self> {
+        PowNode::new(self, exponent)
+    }
+
+    fn clamp(self, min: f64, max: f64) -> ClampNode<DIM, Self> {
+        ClampNode::new(self, min, max)
+    }
+
+    fn min<Rhs>(self, rhs: Rhs) -> MinNode<DIM, Self, Rhs>
+    where Rhs: NoiseNode<DIM> {
+        MinNode::new(self, rhs)
+    }
+
+    fn max<Rhs>(self, rhs: Rhs) -> MaxNode<DIM, Self, Rhs>
+    where Rhs: NoiseNode<DIM> {
+        MaxNode::new(self, rhs)
+    }
+
+    fn cached(self) -> CacheNode<DIM, Self> {
+        CacheNode::new(self)
+    }
+
+    fn boxed(self) -> Box<dyn NoiseNode<DIM> + Send + Sync>
+    where Self: Send + Sync + 'static {
+        Box::new(self)
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNodeExt<DIM> for Source
+where Source: NoiseNode<DIM> {}
@@ -1,31 +1,33 @@
 use crate::geometry::RealPoint;
 use super::NoiseNode;
+use super::blend_node::{apply_opacity, BlendMode};
 
-pub struct OverlayNode<'a, const DIM: usize, Lhs, Rhs>
+#[derive(Clone)]
+pub struct OverlayNode<const DIM: usize, Lhs, Rhs>
 where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
-    lhs: &'a Lhs,
-    rhs: &'a Rhs
+    lhs: Lhs,
+    rhs: Rhs,
+    opacity: f64
 }
 
-impl<'a, const DIM: usize, Lhs, Rhs> OverlayNode<'a, DIM, Lhs, Rhs>
+impl<const DIM: usize, Lhs, Rhs> OverlayNode<DIM, Lhs, Rhs>
 where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
-    pub fn new(lhs: &'a Lhs, rhs: &'a Rhs) -> Self {
-        Self { lhs, rhs }
+    pub fn new(lhs: Lhs, rhs: Rhs) -> Self {
+        Self { lhs, rhs, opacity: 1.0 }
+    }
+
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity;
+        self
     }
 }
 
-impl<'a, const DIM: usize, Lhs, Rhs> NoiseNode<DIM> for OverlayNode<'a, DIM, Lhs, Rhs>
+impl<const DIM: usize, Lhs, Rhs> NoiseNode<DIM> for OverlayNode<DIM, Lhs, Rhs>
 where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
         let lhs_value = self.lhs.value_at(point);
         let rhs_value = self.rhs.value_at(point);
 
-        if lhs_value < 0.5 {
-            2.0 * lhs_value * rhs_value
-        } else {
-            let value = (1.0 - lhs_value) * (1.0 - rhs_value);
-
-            value.mul_add(-2.0, 1.0)
-        }
+        apply_opacity(self.opacity, lhs_value, BlendMode::Overlay.apply(lhs_value, rhs_value))
     }
 }
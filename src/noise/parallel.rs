@@ -0,0 +1,111 @@
+use rayon::prelude::*;
+
+use crate::geometry::RealPoint;
+use super::{grid, NoiseNode};
+
+// Grids are split into slabs along their last axis, which are contiguous in the output. Each
+// slab is filled through `NoiseNode::fill_grid_section`, so results match `fill_grid` exactly
+
+pub fn render_region<const DIM: usize, Node>(node: &Node, origin: RealPoint<DIM>, step: RealPoint<DIM>, extent: [usize; DIM], output: &mut [f64])
+where Node: NoiseNode<DIM> + Sync + ?Sized {
+    assert_eq!(output.len(), grid::num_points(extent));
+
+    if let Some((slab_len, slab_extent)) = slab_layout(extent) {
+        output.par_chunks_mut(slab_len).enumerate().for_each(|(slab, slab_output)| {
+            let (start, extent) = slab_bounds(slab, slab_extent, slab_output.len());
+
+            node.fill_grid_section(origin, step, start, extent, slab_output);
+        });
+    };
+}
+
+// For nodes that can't be shared between threads, every worker renders with its own clone
+pub fn render_region_cloned<const DIM: usize, Node>(node: &Node, origin: RealPoint<DIM>, step: RealPoint<DIM>, extent: [usize; DIM], output: &mut [f64])
+where Node: NoiseNode<DIM> + Clone + Send {
+    assert_eq!(output.len(), grid::num_points(extent));
+
+    if let Some((slab_len, slab_extent)) = slab_layout(extent) {
+        output.par_chunks_mut(slab_len).enumerate().for_each_with(node.clone(), |node, (slab, slab_output)| {
+            let (start, extent) = slab_bounds(slab, slab_extent, slab_output.len());
+
+            node.fill_grid_section(origin, step, start, extent, slab_output);
+        });
+    };
+}
+
+// Number of values in each slab, and the extent of every slab but possibly the last. Aims for a
+// few slabs per thread so uneven work still balances out
+fn slab_layout<const DIM: usize>(extent: [usize; DIM]) -> Option<(usize, [usize; DIM])> {
+    let num_points = grid::num_points(extent);
+
+    if num_points == 0 {
+        return None;
+    };
+
+    let last_axis = DIM - 1;
+    let num_slabs = rayon::current_num_threads() * 4;
+
+    let mut slab_extent = extent;
+
+    slab_extent[last_axis] = extent[last_axis].div_ceil(num_slabs).max(1);
+
+    Some((grid::num_points(slab_extent), slab_extent))
+}
+
+// Start and extent of a slab, where only the last slab may be thinner than the others
+fn slab_bounds<const DIM: usize>(slab: usize, slab_extent: [usize; DIM], slab_output_len: usize) -> ([usize; DIM], [usize; DIM]) {
+    let last_axis = DIM - 1;
+    let slice_len = grid::num_points(slab_extent) / slab_extent[last_axis];
+
+    let mut start = [0_usize; DIM];
+    let mut extent = slab_extent;
+
+    start[last_axis] = slab * slab_extent[last_axis];
+    extent[last_axis] = slab_output_len / slice_len;
+
+    (start, extent)
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{EuclideanMetric, RealPoint};
+    use crate::noise::{grid, NoiseNode, PerlinNode, WorleyNode, WorleyPaintMethod};
+    use super::{render_region, render_region_cloned};
+
+    #[test]
+    fn matches_serial() {
+        let node = PerlinNode::<3>::new(1);
+
+        let origin = RealPoint::<3>::new([-4.1, 2.7, 0.3]);
+        let step = RealPoint::<3>::new([0.11, 0.07, 0.13]);
+        let extent = [19, 23, 37];
+
+        let mut serial = vec![0.0; grid::num_points(extent)];
+        let mut parallel = vec![0.0; grid::num_points(extent)];
+
+        node.fill_grid(origin, step, extent, &mut serial);
+        render_region(&node, origin, step, extent, &mut parallel);
+
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn cloned_matches_serial() {
+        let node = WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::F1);
+
+        let origin = RealPoint::<2>::new([-4.1, 2.7]);
+        let step = RealPoint::<2>::new([0.11, 0.07]);
+        let extent = [31, 45];
+
+        let mut serial = vec![0.0; grid::num_points(extent)];
+        let mut parallel = vec![0.0; grid::num_points(extent)];
+
+        node.fill_grid(origin, step, extent, &mut serial);
+        render_region_cloned(&node, origin, step, extent, &mut parallel);
+
+        assert_eq!(serial, parallel);
+    }
+}
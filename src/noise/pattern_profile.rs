@@ -0,0 +1,22 @@
+use crate::utils;
+
+// Shapes the falloff of the pattern nodes, mapping [0.0, 1.0] -> [0.0, 1.0] with both ends fixed
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PatternProfile {
+    Linear,
+    Smoothstep,
+
+    // `utils::sigmoid` with the given beta. Negative betas ease in and out, positive ones
+    // sharpen the middle
+    Sigmoid(f64)
+}
+
+impl PatternProfile {
+    pub fn apply(self, x: f64) -> f64 {
+        match self {
+            PatternProfile::Linear => x,
+            PatternProfile::Smoothstep => utils::smoothstep(x),
+            PatternProfile::Sigmoid(beta) => utils::sigmoid(beta, x)
+        }
+    }
+}
@@ -3,12 +3,18 @@ use itertools::Itertools;
 use crate::geometry::RealPoint;
 use crate::random::StatelessRand;
 use crate::utils;
-use super::NoiseNode;
-use super::function::{gen_gradients, perlin_1d, perlin_2d, perlin_3d};
-
+use super::{grid, NoiseNode, PeriodicNode};
+use super::function::{
+    cell_gradients_2d, cell_gradients_3d, gen_gradients, lerp_vertices_with_gradient, perlin_1d,
+    perlin_1d_with_gradient, perlin_2d, perlin_2d_in_cell, perlin_2d_with_gradient, perlin_3d,
+    perlin_3d_in_cell, perlin_3d_with_gradient, wrap_lattice
+};
+
+#[derive(Clone)]
 pub struct PerlinNode<const DIM: usize> {
     rng: StatelessRand,
-    gradients: Vec<RealPoint<DIM>>
+    gradients: Vec<RealPoint<DIM>>,
+    period: Option<RealPoint<DIM>>
 }
 
 impl<const DIM: usize> PerlinNode<DIM> {
@@ -21,7 +27,7 @@ impl<const DIM: usize> PerlinNode<DIM> {
             gen_gradients(seed, Self::NUM_GRADIENTS)
         };
 
-        Self { rng: StatelessRand::from_seed(seed), gradients }
+        Self { rng: StatelessRand::from_seed(seed), gradients, period: None }
     }
 
     // N-dimensional Perlin noise generates values in the range [-X, X], where X = sqrt(N) / 2
@@ -30,32 +36,150 @@ impl<const DIM: usize> PerlinNode<DIM> {
     }
 
     pub fn noise_value_for(&self, point: RealPoint<DIM>, vertex: RealPoint<DIM>) -> f64 {
-        let hash = self.rng.hash_bytes(vertex.as_bytes()) as usize;
-        let gradient = self.gradients[hash % Self::NUM_GRADIENTS];
         let inner_point = vertex - point;
 
-        inner_point.dot_product(gradient)
+        inner_point.dot_product(self.gradient_for(vertex))
+    }
+
+    fn gradient_for(&self, mut vertex: RealPoint<DIM>) -> RealPoint<DIM> {
+        if let Some(period) = self.period {
+            for (coord, &period) in vertex.iter_mut().zip(period.iter()) {
+                *coord = wrap_lattice(*coord, period);
+            };
+        };
+
+        let hash = self.rng.hash_bytes(vertex.as_bytes()) as usize;
+
+        self.gradients[hash % Self::NUM_GRADIENTS]
     }
 }
 
 impl NoiseNode<1> for PerlinNode<1> {
     fn value_at(&self, point: RealPoint<1>) -> f64 {
-        perlin_1d(self.rng, point)
+        perlin_1d(self.rng, self.period, point)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<1>) -> (f64, RealPoint<1>) {
+        perlin_1d_with_gradient(self.rng, self.period, point)
     }
 }
 
 impl NoiseNode<2> for PerlinNode<2> {
     fn value_at(&self, point: RealPoint<2>) -> f64 {
-        perlin_2d(self.rng, point)
+        perlin_2d(self.rng, self.period, point)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<2>) -> (f64, RealPoint<2>) {
+        perlin_2d_with_gradient(self.rng, self.period, point)
+    }
+
+    // Every column keeps its lattice cell along x, so the gradients of a row of cells only need
+    // hashing again once a row crosses into the next cell along y
+    fn fill_grid_section(&self, origin: RealPoint<2>, step: RealPoint<2>, start: [usize; 2], extent: [usize; 2], output: &mut [f64]) {
+        assert_eq!(output.len(), grid::num_points(extent));
+
+        if output.is_empty() {
+            return;
+        };
+
+        let columns = lattice_columns(origin[0], step[0], start[0], extent[0]);
+
+        let mut gradients: Vec<[(f64, f64); 4]> = Vec::with_capacity(extent[0]);
+        let mut current_ay0 = f64::NAN;
+
+        for (y, row) in output.chunks_exact_mut(extent[0]).enumerate() {
+            let py = grid::grid_coordinate(origin[1], step[1], start[1] + y);
+            let ay0 = py.floor();
+
+            if ay0 != current_ay0 {
+                gradients.clear();
+
+                let mut current_ax0 = f64::NAN;
+                let mut cell_gradients = [(0.0, 0.0); 4];
+
+                for &(ax0, _) in columns.iter() {
+                    if ax0 != current_ax0 {
+                        cell_gradients = cell_gradients_2d(self.rng, self.period, ax0, ay0);
+                        current_ax0 = ax0;
+                    };
+
+                    gradients.push(cell_gradients);
+                };
+
+                current_ay0 = ay0;
+            };
+
+            for (value, (&(_, nx0), cell_gradients)) in row.iter_mut().zip(columns.iter().zip(&gradients)) {
+                *value = perlin_2d_in_cell(cell_gradients, nx0, py - ay0);
+            };
+        };
     }
 }
 
 impl NoiseNode<3> for PerlinNode<3> {
     fn value_at(&self, point: RealPoint<3>) -> f64 {
-        perlin_3d(self.rng, point)
+        perlin_3d(self.rng, self.period, point)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<3>) -> (f64, RealPoint<3>) {
+        perlin_3d_with_gradient(self.rng, self.period, point)
+    }
+
+    // As in 2D, a row of cells is only hashed again once a row crosses into a new cell along y or z
+    fn fill_grid_section(&self, origin: RealPoint<3>, step: RealPoint<3>, start: [usize; 3], extent: [usize; 3], output: &mut [f64]) {
+        assert_eq!(output.len(), grid::num_points(extent));
+
+        if output.is_empty() {
+            return;
+        };
+
+        let columns = lattice_columns(origin[0], step[0], start[0], extent[0]);
+
+        let mut gradients: Vec<[(f64, f64, f64); 8]> = Vec::with_capacity(extent[0]);
+        let mut current_cell = (f64::NAN, f64::NAN);
+
+        for (row_index, row) in output.chunks_exact_mut(extent[0]).enumerate() {
+            let py = grid::grid_coordinate(origin[1], step[1], start[1] + row_index % extent[1]);
+            let pz = grid::grid_coordinate(origin[2], step[2], start[2] + row_index / extent[1]);
+
+            let ay0 = py.floor();
+            let az0 = pz.floor();
+
+            if (ay0, az0) != current_cell {
+                gradients.clear();
+
+                let mut current_ax0 = f64::NAN;
+                let mut cell_gradients = [(0.0, 0.0, 0.0); 8];
+
+                for &(ax0, _) in columns.iter() {
+                    if ax0 != current_ax0 {
+                        cell_gradients = cell_gradients_3d(self.rng, self.period, ax0, ay0, az0);
+                        current_ax0 = ax0;
+                    };
+
+                    gradients.push(cell_gradients);
+                };
+
+                current_cell = (ay0, az0);
+            };
+
+            for (value, (&(_, nx0), cell_gradients)) in row.iter_mut().zip(columns.iter().zip(&gradients)) {
+                *value = perlin_3d_in_cell(cell_gradients, nx0, py - ay0, pz - az0);
+            };
+        };
     }
 }
 
+// The lattice cell (lowest vertex) and offset into it of every column of a grid along x
+fn lattice_columns(origin: f64, step: f64, start: usize, width: usize) -> Vec<(f64, f64)> {
+    (start..(start + width)).map(|x| {
+        let px = grid::grid_coordinate(origin, step, x);
+        let ax0 = px.floor();
+
+        (ax0, px - ax0)
+    }).collect()
+}
+
 impl<const DIM: usize> NoiseNode<DIM> for PerlinNode<DIM> {
     default fn value_at(&self, point: RealPoint<DIM>) -> f64 {
         let mut noise_values: Vec<f64> = point.vertex_neighborhood().map(|vertex| {
@@ -81,4 +205,47 @@ impl<const DIM: usize> NoiseNode<DIM> for PerlinNode<DIM> {
 
         utils::smoothstep(utils::neg_unit_to_unit(noise_value))
     }
+
+    default fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        grid::fill_per_point(self, origin, step, start, extent, output)
+    }
+
+    default fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let mut noise_values: Vec<f64> = point.vertex_neighborhood().map(|vertex| {
+            self.noise_value_for(point, vertex)
+        }).collect();
+
+        // Offsets run from the point to each vertex, so vertex values fall along their gradients
+        let mut noise_gradients: Vec<RealPoint<DIM>> = point.vertex_neighborhood().map(|vertex| {
+            self.gradient_for(vertex) * -1.0
+        }).collect();
+
+        let offset = point - point.floor();
+
+        let mut biases = RealPoint::<DIM>::origin();
+        let mut slopes = RealPoint::<DIM>::origin();
+
+        for dim in 0..DIM {
+            biases[dim] = utils::smoothstep(offset[dim]);
+            slopes[dim] = utils::smoothstep_derivative(offset[dim]);
+        };
+
+        let (noise_value, noise_gradient) = lerp_vertices_with_gradient(&mut noise_values, &mut noise_gradients, biases, slopes);
+
+        // `unbias` is linear, so it scales the gradient by its value at 1.0
+        let unit_value = utils::neg_unit_to_unit(Self::unbias(noise_value));
+        let slope = utils::smoothstep_derivative(unit_value) * 0.5 * Self::unbias(1.0);
+
+        (utils::smoothstep(unit_value), noise_gradient * slope)
+    }
+}
+
+// Wrapping happens on the lattice, so the period counts whole cells along each axis
+impl<const DIM: usize> PeriodicNode<DIM> for PerlinNode<DIM> {
+    fn with_period(mut self, period: [u32; DIM]) -> Self {
+        assert!(period.iter().all(|&cells| cells > 0), "periods must span at least one cell");
+
+        self.period = Some(RealPoint::<DIM>::new(period));
+        self
+    }
 }
@@ -0,0 +1,23 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+#[derive(Clone)]
+pub struct PowNode<const DIM: usize, Source>
+where Source: NoiseNode<DIM> {
+    source: Source,
+    exponent: f64
+}
+
+impl<const DIM: usize, Source> PowNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    pub fn new(source: Source, exponent: f64) -> Self {
+        Self { source, exponent }
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for PowNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        self.source.value_at(point).powf(self.exponent)
+    }
+}
@@ -0,0 +1,57 @@
+use std::marker::PhantomData;
+
+use crate::geometry::{DistanceMetric, RealPoint};
+use super::{NoiseNode, PatternProfile};
+
+// Ramps from 0.0 at `center` to 1.0 at `radius` away from it, and stays at 1.0 beyond
+#[derive(Clone)]
+pub struct RadialGradientNode<const DIM: usize, Metric>
+where Metric: DistanceMetric {
+    center: RealPoint<DIM>,
+    radius: f64,
+    profile: PatternProfile,
+    phantom: PhantomData<Metric>
+}
+
+impl<const DIM: usize, Metric> RadialGradientNode<DIM, Metric>
+where Metric: DistanceMetric {
+    pub fn new(center: RealPoint<DIM>, radius: f64) -> Self {
+        assert!(radius > 0.0, "radial gradients need a positive radius");
+
+        Self { center, radius, profile: PatternProfile::Linear, phantom: PhantomData }
+    }
+
+    pub fn with_profile(mut self, profile: PatternProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+}
+
+impl<const DIM: usize, Metric> NoiseNode<DIM> for RadialGradientNode<DIM, Metric>
+where Metric: DistanceMetric {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let distance = (point - self.center).magnitude::<Metric>();
+
+        self.profile.apply((distance / self.radius).min(1.0))
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{EuclideanMetric, RealPoint};
+    use crate::noise::NoiseNode;
+    use super::RadialGradientNode;
+
+    #[test]
+    fn ramp() {
+        let at = |x: f64, y: f64| RealPoint::<2>::new([x, y]);
+        let radial = RadialGradientNode::<2, EuclideanMetric>::new(at(1.0, 1.0), 2.0);
+
+        assert_eq!(radial.value_at(at(1.0, 1.0)), 0.0);
+        assert_eq!(radial.value_at(at(1.0, 2.0)), 0.5);
+        assert_eq!(radial.value_at(at(9.0, 1.0)), 1.0);
+    }
+}
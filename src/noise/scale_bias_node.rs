@@ -0,0 +1,30 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+#[derive(Clone)]
+pub struct ScaleBiasNode<const DIM: usize, Source>
+where Source: NoiseNode<DIM> {
+    source: Source,
+    scale: f64,
+    bias: f64
+}
+
+impl<const DIM: usize, Source> ScaleBiasNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    pub fn new(source: Source, scale: f64, bias: f64) -> Self {
+        Self { source, scale, bias }
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for ScaleBiasNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        self.source.value_at(point).mul_add(self.scale, self.bias)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (value, gradient) = self.source.value_and_gradient(point);
+
+        (value.mul_add(self.scale, self.bias), gradient * self.scale)
+    }
+}
@@ -1,25 +1,33 @@
 use crate::geometry::RealPoint;
 use super::NoiseNode;
+use super::blend_node::{apply_opacity, BlendMode};
 
-pub struct ScreenNode<'a, const DIM: usize, Lhs, Rhs>
+#[derive(Clone)]
+pub struct ScreenNode<const DIM: usize, Lhs, Rhs>
 where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
-    lhs: &'a Lhs,
-    rhs: &'a Rhs
+    lhs: Lhs,
+    rhs: Rhs,
+    opacity: f64
 }
 
-impl<'a, const DIM: usize, Lhs, Rhs> ScreenNode<'a, DIM, Lhs, Rhs>
+impl<const DIM: usize, Lhs, Rhs> ScreenNode<DIM, Lhs, Rhs>
 where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
-    pub fn new(lhs: &'a Lhs, rhs: &'a Rhs) -> Self {
-        Self { lhs, rhs }
+    pub fn new(lhs: Lhs, rhs: Rhs) -> Self {
+        Self { lhs, rhs, opacity: 1.0 }
+    }
+
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity;
+        self
     }
 }
 
-impl<'a, const DIM: usize, Lhs, Rhs> NoiseNode<DIM> for ScreenNode<'a, DIM, Lhs, Rhs>
+impl<const DIM: usize, Lhs, Rhs> NoiseNode<DIM> for ScreenNode<DIM, Lhs, Rhs>
 where Lhs: NoiseNode<DIM>, Rhs: NoiseNode<DIM> {
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
         let lhs_value = self.lhs.value_at(point);
         let rhs_value = self.rhs.value_at(point);
 
-        1.0 - (1.0 - lhs_value) * (1.0 - rhs_value)
+        apply_opacity(self.opacity, lhs_value, BlendMode::Screen.apply(lhs_value, rhs_value))
     }
 }
@@ -0,0 +1,140 @@
+use crate::geometry::RealPoint;
+use crate::utils;
+use super::NoiseNode;
+
+// Picks `inside` wherever `control` lies within [lower, upper] and `outside` everywhere else. A
+// falloff blends the two smoothly across a band of that half-width around either bound. Only the
+// sources that contribute at a point are evaluated, so expensive branches cost nothing where
+// they aren't selected
+#[derive(Clone)]
+pub struct SelectNode<const DIM: usize, Outside, Inside, Control>
+where Outside: NoiseNode<DIM>, Inside: NoiseNode<DIM>, Control: NoiseNode<DIM> {
+    outside: Outside,
+    inside: Inside,
+    control: Control,
+    lower: f64,
+    upper: f64,
+    falloff: f64
+}
+
+impl<const DIM: usize, Outside, Inside, Control> SelectNode<DIM, Outside, Inside, Control>
+where Outside: NoiseNode<DIM>, Inside: NoiseNode<DIM>, Control: NoiseNode<DIM> {
+    pub fn new(outside: Outside, inside: Inside, control: Control, lower: f64, upper: f64) -> Self {
+        assert!(lower <= upper, "selections need lower <= upper");
+
+        Self { outside, inside, control, lower, upper, falloff: 0.0 }
+    }
+
+    // Falloffs are capped at half the selected range, so the two edges never overlap
+    pub fn with_falloff(mut self, falloff: f64) -> Self {
+        self.falloff = falloff.clamp(0.0, (self.upper - self.lower) / 2.0);
+        self
+    }
+
+    // How much of `inside` shows at a control value, and how fast that changes with it
+    fn inside_weight(&self, control: f64) -> (f64, f64) {
+        let falloff = self.falloff;
+
+        if falloff == 0.0 {
+            let weight = if (self.lower..=self.upper).contains(&control) { 1.0 } else { 0.0 };
+
+            return (weight, 0.0);
+        };
+
+        let edge = |start: f64| {
+            let bias = (control - start) / (2.0 * falloff);
+
+            (utils::smoothstep(bias), utils::smoothstep_derivative(bias) / (2.0 * falloff))
+        };
+
+        if control <= self.lower - falloff || control >= self.upper + falloff {
+            (0.0, 0.0)
+        } else if control < self.lower + falloff {
+            edge(self.lower - falloff)
+        } else if control <= self.upper - falloff {
+            (1.0, 0.0)
+        } else {
+            let (weight, slope) = edge(self.upper - falloff);
+
+            (1.0 - weight, -slope)
+        }
+    }
+}
+
+impl<const DIM: usize, Outside, Inside, Control> NoiseNode<DIM> for SelectNode<DIM, Outside, Inside, Control>
+where Outside: NoiseNode<DIM>, Inside: NoiseNode<DIM>, Control: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let (weight, _) = self.inside_weight(self.control.value_at(point));
+
+        if weight == 0.0 {
+            self.outside.value_at(point)
+        } else if weight == 1.0 {
+            self.inside.value_at(point)
+        } else {
+            utils::lerp(weight, self.outside.value_at(point), self.inside.value_at(point))
+        }
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (control, control_gradient) = self.control.value_and_gradient(point);
+        let (weight, slope) = self.inside_weight(control);
+
+        if weight == 0.0 {
+            return self.outside.value_and_gradient(point);
+        };
+
+        if weight == 1.0 {
+            return self.inside.value_and_gradient(point);
+        };
+
+        let (outside, outside_gradient) = self.outside.value_and_gradient(point);
+        let (inside, inside_gradient) = self.inside.value_and_gradient(point);
+
+        let gradient = outside_gradient + (inside_gradient - outside_gradient) * weight + control_gradient * (slope * (inside - outside));
+
+        (utils::lerp(weight, outside, inside), gradient)
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use crate::geometry::RealPoint;
+    use crate::noise::{ConstNode, NoiseNode};
+    use super::SelectNode;
+
+    struct CountingNode {
+        value: f64,
+        count: Cell<usize>
+    }
+
+    impl NoiseNode<1> for CountingNode {
+        fn value_at(&self, _: RealPoint<1>) -> f64 {
+            self.count.set(self.count.get() + 1);
+            self.value
+        }
+    }
+
+    #[test]
+    fn evaluates_selected_branches() {
+        let outside = CountingNode { value: 0.0, count: Cell::new(0) };
+        let inside = CountingNode { value: 1.0, count: Cell::new(0) };
+        let point = RealPoint::<1>::new([0.0]);
+
+        let select = |control: f64| SelectNode::new(&outside, &inside, ConstNode::new(control), 0.4, 0.6).with_falloff(0.05);
+
+        assert_eq!(select(0.2).value_at(point), 0.0);
+        assert_eq!(select(0.5).value_at(point), 1.0);
+        assert_eq!(select(0.9).value_at(point), 0.0);
+        assert_eq!((outside.count.get(), inside.count.get()), (2, 1));
+
+        // Right on a bound, the two sources are blended evenly
+        assert!((select(0.4).value_at(point) - 0.5).abs() < 1e-9);
+        assert!((select(0.6).value_at(point) - 0.5).abs() < 1e-9);
+        assert_eq!((outside.count.get(), inside.count.get()), (4, 3));
+    }
+}
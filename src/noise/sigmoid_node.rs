@@ -2,22 +2,29 @@ use crate::geometry::RealPoint;
 use crate::utils;
 use super::NoiseNode;
 
-pub struct SigmoidNode<'a, const DIM: usize, Source>
+#[derive(Clone)]
+pub struct SigmoidNode<const DIM: usize, Source>
 where Source: NoiseNode<DIM> {
-    source: &'a Source,
+    source: Source,
     beta: f64
 }
 
-impl<'a, const DIM: usize, Source> SigmoidNode<'a, DIM, Source>
+impl<const DIM: usize, Source> SigmoidNode<DIM, Source>
 where Source: NoiseNode<DIM> {
-    pub fn new(source: &'a Source, beta: f64) -> Self {
+    pub fn new(source: Source, beta: f64) -> Self {
         Self { source, beta }
     }
 }
 
-impl<'a, const DIM: usize, Source> NoiseNode<DIM> for SigmoidNode<'a, DIM, Source>
+impl<const DIM: usize, Source> NoiseNode<DIM> for SigmoidNode<DIM, Source>
 where Source: NoiseNode<DIM> {
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
         utils::sigmoid(self.beta, self.source.value_at(point))
     }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (value, gradient) = self.source.value_and_gradient(point);
+
+        (utils::sigmoid(self.beta, value), gradient * utils::sigmoid_derivative(self.beta, value))
+    }
 }
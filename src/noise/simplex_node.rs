@@ -0,0 +1,186 @@
+use std::iter;
+
+use crate::geometry::RealPoint;
+use crate::random::StatelessRand;
+use crate::utils;
+use super::NoiseNode;
+use super::function::{
+    gen_gradients, simplex_2d, simplex_2d_with_gradient, simplex_3d, simplex_3d_with_gradient,
+    simplex_4d, simplex_4d_with_gradient, simplex_vertex, simplex_vertex_with_gradient,
+    SIMPLEX_BIAS_1D, SIMPLEX_BIAS_2D, SIMPLEX_BIAS_3D, SIMPLEX_BIAS_4D, SIMPLEX_BIAS_HIGH_DIM
+};
+
+#[derive(Clone)]
+pub struct SimplexNode<const DIM: usize> {
+    rng: StatelessRand,
+    gradients: Vec<RealPoint<DIM>>,
+    skew: f64,
+    unskew: f64
+}
+
+impl<const DIM: usize> SimplexNode<DIM> {
+    const NUM_GRADIENTS: usize = 2_usize.pow((DIM as u32) + 3);
+
+    pub fn new(seed: u64) -> Self {
+        let gradients: Vec<RealPoint<DIM>> = if DIM > 1 && DIM < 5 {
+            Vec::with_capacity(0)
+        } else {
+            gen_gradients(seed, Self::NUM_GRADIENTS)
+        };
+
+        let num_vertices = (DIM as f64) + 1.0;
+
+        let skew = (num_vertices.sqrt() - 1.0) / (DIM as f64);
+        let unskew = (1.0 - num_vertices.sqrt().recip()) / (DIM as f64);
+
+        Self { rng: StatelessRand::from_seed(seed), gradients, skew, unskew }
+    }
+
+    // N-dimensional simplex noise generates values in the range [-1 / X, 1 / X], where X
+    // approaches ~108.73 from below as N grows
+    pub fn unbias(x: f64) -> f64 {
+        match DIM {
+            1 => x * SIMPLEX_BIAS_1D,
+            2 => x * SIMPLEX_BIAS_2D,
+            3 => x * SIMPLEX_BIAS_3D,
+            4 => x * SIMPLEX_BIAS_4D,
+            _ => x * SIMPLEX_BIAS_HIGH_DIM
+        }
+    }
+
+    pub fn noise_value_for(&self, vertex: RealPoint<DIM>, offset: RealPoint<DIM>) -> f64 {
+        simplex_vertex(offset, || self.gradient_for(vertex))
+    }
+
+    fn gradient_for(&self, vertex: RealPoint<DIM>) -> RealPoint<DIM> {
+        let hash = self.rng.hash_bytes(vertex.as_bytes()) as usize;
+
+        self.gradients[hash % Self::NUM_GRADIENTS]
+    }
+
+    // Every vertex of the simplex containing the point, along with the point's offset from it
+    fn simplex_vertices(&self, point: RealPoint<DIM>) -> impl Iterator<Item = (RealPoint<DIM>, RealPoint<DIM>)> + '_ {
+        let skew = point.sum() * self.skew;
+        let cell = (point + skew).floor();
+        let unskew = cell.sum() * self.unskew;
+
+        let offset = point - cell + unskew;
+
+        let mut axes = [0_usize; DIM];
+
+        for (idx, axis) in axes.iter_mut().enumerate() {
+            *axis = idx;
+        };
+
+        // Walking the axes from the largest offset to the smallest visits the simplex's vertices
+        axes.sort_by(|&lhs, &rhs| offset[rhs].total_cmp(&offset[lhs]));
+
+        let steps = axes.into_iter().enumerate().scan(RealPoint::<DIM>::origin(), move |increment, (step, axis)| {
+            increment[axis] = 1.0;
+
+            Some((cell + *increment, offset - *increment + ((step + 1) as f64) * self.unskew))
+        });
+
+        iter::once((cell, offset)).chain(steps)
+    }
+}
+
+impl NoiseNode<2> for SimplexNode<2> {
+    fn value_at(&self, point: RealPoint<2>) -> f64 {
+        simplex_2d(self.rng, point)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<2>) -> (f64, RealPoint<2>) {
+        simplex_2d_with_gradient(self.rng, point)
+    }
+}
+
+impl NoiseNode<3> for SimplexNode<3> {
+    fn value_at(&self, point: RealPoint<3>) -> f64 {
+        simplex_3d(self.rng, point)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<3>) -> (f64, RealPoint<3>) {
+        simplex_3d_with_gradient(self.rng, point)
+    }
+}
+
+impl NoiseNode<4> for SimplexNode<4> {
+    fn value_at(&self, point: RealPoint<4>) -> f64 {
+        simplex_4d(self.rng, point)
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<4>) -> (f64, RealPoint<4>) {
+        simplex_4d_with_gradient(self.rng, point)
+    }
+}
+
+impl<const DIM: usize> NoiseNode<DIM> for SimplexNode<DIM> {
+    default fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let noise_value: f64 = self.simplex_vertices(point).map(|(vertex, offset)| {
+            self.noise_value_for(vertex, offset)
+        }).sum();
+
+        utils::neg_unit_to_unit(Self::unbias(noise_value))
+    }
+
+    default fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let mut noise_value = 0.0;
+        let mut noise_gradient = RealPoint::<DIM>::origin();
+
+        for (vertex, offset) in self.simplex_vertices(point) {
+            let (vertex_value, vertex_gradient) = simplex_vertex_with_gradient(offset, || self.gradient_for(vertex));
+
+            noise_value += vertex_value;
+            noise_gradient += vertex_gradient;
+        };
+
+        // `unbias` is linear, so it scales the gradient by its value at 1.0
+        let slope = 0.5 * Self::unbias(1.0);
+
+        (utils::neg_unit_to_unit(Self::unbias(noise_value)), noise_gradient * slope)
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::NoiseNode;
+    use crate::random::StatefulRand;
+    use super::SimplexNode;
+
+    fn assert_in_range<const DIM: usize>() {
+        let node = SimplexNode::<DIM>::new(1);
+        let mut rng = StatefulRand::from_seed(1);
+
+        for _ in 0..20000 {
+            let mut point = RealPoint::<DIM>::origin();
+
+            for coord in point.iter_mut() {
+                *coord = rng.get_f64() * 200.0 - 100.0;
+            };
+
+            let value = node.value_at(point);
+
+            assert!((0.0..=1.0).contains(&value), "{}D gave {} at {}", DIM, value, point);
+        };
+    }
+
+    #[test]
+    fn in_range() {
+        assert_in_range::<1>();
+        assert_in_range::<2>();
+        assert_in_range::<3>();
+        assert_in_range::<4>();
+        assert_in_range::<5>();
+    }
+
+    #[test]
+    fn nan_coordinates() {
+        assert!(SimplexNode::<2>::new(1).value_at(RealPoint::<2>::new([f64::NAN, 0.5])).is_nan());
+        assert!(SimplexNode::<5>::new(1).value_at(RealPoint::<5>::new([0.5, f64::NAN, 0.5, 0.5, 0.5])).is_nan());
+    }
+}
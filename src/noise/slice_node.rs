@@ -0,0 +1,97 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+// Samples a higher-dimensional source on the flat subspace through `origin` spanned by `basis`,
+// so `point` maps to `origin + point[0] * basis[0] + point[1] * basis[1] + ...`. A 2D slice of 3D
+// noise is a texture, and moving the origin along the remaining axis animates it
+#[derive(Clone)]
+pub struct SliceNode<const DIM: usize, const SOURCE_DIM: usize, Source>
+where Source: NoiseNode<SOURCE_DIM> {
+    source: Source,
+    origin: RealPoint<SOURCE_DIM>,
+    basis: [RealPoint<SOURCE_DIM>; DIM]
+}
+
+impl<const DIM: usize, const SOURCE_DIM: usize, Source> SliceNode<DIM, SOURCE_DIM, Source>
+where Source: NoiseNode<SOURCE_DIM> {
+    pub fn new(source: Source, origin: RealPoint<SOURCE_DIM>, basis: [RealPoint<SOURCE_DIM>; DIM]) -> Self {
+        Self { source, origin, basis }
+    }
+
+    // The slice through `origin` spanned by the first DIM axes of the source
+    pub fn axis_aligned(source: Source, origin: RealPoint<SOURCE_DIM>) -> Self {
+        assert!(DIM <= SOURCE_DIM, "slices can't have more dimensions than their source");
+
+        let basis = std::array::from_fn(|dim| {
+            let mut axis = RealPoint::<SOURCE_DIM>::origin();
+
+            axis[dim] = 1.0;
+            axis
+        });
+
+        Self::new(source, origin, basis)
+    }
+
+    pub fn with_origin(mut self, origin: RealPoint<SOURCE_DIM>) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn embed(&self, point: RealPoint<DIM>) -> RealPoint<SOURCE_DIM> {
+        self.basis.iter().zip(point.iter()).fold(self.origin, |embedded, (&axis, &coord)| {
+            embedded + axis * coord
+        })
+    }
+}
+
+impl<const DIM: usize, const SOURCE_DIM: usize, Source> NoiseNode<DIM> for SliceNode<DIM, SOURCE_DIM, Source>
+where Source: NoiseNode<SOURCE_DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        self.source.value_at(self.embed(point))
+    }
+
+    // Moving along an input axis moves along its basis vector in the source
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (value, source_gradient) = self.source.value_and_gradient(self.embed(point));
+
+        let mut gradient = RealPoint::<DIM>::origin();
+
+        for (coord, &axis) in gradient.iter_mut().zip(self.basis.iter()) {
+            *coord = axis.dot_product(source_gradient);
+        };
+
+        (value, gradient)
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::{ExtrudeNode, NoiseNode, SimplexNode};
+    use super::SliceNode;
+
+    #[test]
+    fn slice_and_extrude() {
+        let source = SimplexNode::<3>::new(1);
+        let texture = SimplexNode::<2>::new(1);
+
+        let slice = SliceNode::axis_aligned(&source, RealPoint::<3>::new([0.0, 0.0, 2.5]));
+        let diagonal = SliceNode::new(&source, RealPoint::<3>::origin(), [RealPoint::<3>::new([1.0, 1.0, 1.0])]);
+
+        let extruded = ExtrudeNode::<3, 2, _>::new(&texture, [0, 1]);
+        let swapped = ExtrudeNode::<3, 2, _>::new(&texture, [2, 0]);
+
+        for idx in 0..100 {
+            let t = (idx as f64) * 0.37 - 20.0;
+
+            assert_eq!(slice.value_at(RealPoint::<2>::new([t, -t])), source.value_at(RealPoint::<3>::new([t, -t, 2.5])));
+            assert_eq!(diagonal.value_at(RealPoint::<1>::new([t])), source.value_at(RealPoint::<3>::new([t, t, t])));
+
+            assert_eq!(extruded.value_at(RealPoint::<3>::new([t, 1.5, t * 2.0])), texture.value_at(RealPoint::<2>::new([t, 1.5])));
+            assert_eq!(swapped.value_at(RealPoint::<3>::new([t, 1.5, -t])), texture.value_at(RealPoint::<2>::new([-t, t])));
+        };
+    }
+}
@@ -0,0 +1,103 @@
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CubeFace {
+    PositiveX,
+    NegativeX,
+    PositiveY,
+    NegativeY,
+    PositiveZ,
+    NegativeZ
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SphereMapping {
+    // Input is (longitude, latitude) in radians, with latitude in [-pi / 2, pi / 2]
+    LatLong,
+
+    // Input is (u, v) in [-1.0, 1.0] on one face of a cube map, oriented as in OpenGL
+    CubeFace(CubeFace)
+}
+
+// Samples a 3D source on the surface of a sphere centred on the origin, which gives seamless
+// planet maps without the pinching of wrapping a 2D source
+#[derive(Clone)]
+pub struct SphereNode<Source>
+where Source: NoiseNode<3> {
+    source: Source,
+    radius: f64,
+    mapping: SphereMapping
+}
+
+impl<Source> SphereNode<Source>
+where Source: NoiseNode<3> {
+    pub fn new(source: Source, radius: f64, mapping: SphereMapping) -> Self {
+        Self { source, radius, mapping }
+    }
+
+    pub fn embed(&self, point: RealPoint<2>) -> RealPoint<3> {
+        let direction = match self.mapping {
+            SphereMapping::LatLong => {
+                let (longitude, latitude) = (point[0], point[1]);
+
+                RealPoint::<3>::new([
+                    latitude.cos() * longitude.cos(),
+                    latitude.cos() * longitude.sin(),
+                    latitude.sin()
+                ])
+            },
+            SphereMapping::CubeFace(face) => {
+                let (u, v) = (point[0], point[1]);
+
+                let cube_point = match face {
+                    CubeFace::PositiveX => [1.0, -v, -u],
+                    CubeFace::NegativeX => [-1.0, -v, u],
+                    CubeFace::PositiveY => [u, 1.0, v],
+                    CubeFace::NegativeY => [u, -1.0, -v],
+                    CubeFace::PositiveZ => [u, -v, 1.0],
+                    CubeFace::NegativeZ => [-u, -v, -1.0]
+                };
+
+                RealPoint::<3>::new(cube_point).normalize()
+            }
+        };
+
+        direction * self.radius
+    }
+}
+
+impl<Source> NoiseNode<2> for SphereNode<Source>
+where Source: NoiseNode<3> {
+    fn value_at(&self, point: RealPoint<2>) -> f64 {
+        self.source.value_at(self.embed(point))
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::{NoiseNode, SimplexNode};
+    use super::{CubeFace, SphereMapping, SphereNode};
+
+    #[test]
+    fn mappings_agree() {
+        let source = SimplexNode::<3>::new(1);
+
+        let lat_long = SphereNode::new(&source, 2.0, SphereMapping::LatLong);
+        let positive_x = SphereNode::new(&source, 2.0, SphereMapping::CubeFace(CubeFace::PositiveX));
+        let negative_z = SphereNode::new(&source, 2.0, SphereMapping::CubeFace(CubeFace::NegativeZ));
+
+        assert_eq!(positive_x.value_at(RealPoint::<2>::new([0.0, 0.0])), lat_long.value_at(RealPoint::<2>::new([0.0, 0.0])));
+
+        // The right edge of +X is the left edge of -Z
+        for idx in 0..10 {
+            let v = (idx as f64) * 0.2 - 1.0;
+
+            assert_eq!(positive_x.value_at(RealPoint::<2>::new([1.0, v])), negative_z.value_at(RealPoint::<2>::new([-1.0, v])));
+        };
+    }
+}
@@ -0,0 +1,58 @@
+use std::marker::PhantomData;
+
+use crate::geometry::{DistanceMetric, RealPoint};
+use super::{NoiseNode, PatternProfile};
+
+// A sphere centred in every lattice cell, 1.0 at its centre and falling off to 0.0 where it
+// touches the faces of the cell
+#[derive(Clone)]
+pub struct SpheresNode<const DIM: usize, Metric>
+where Metric: DistanceMetric {
+    frequency: f64,
+    profile: PatternProfile,
+    phantom: PhantomData<Metric>
+}
+
+impl<const DIM: usize, Metric> SpheresNode<DIM, Metric>
+where Metric: DistanceMetric {
+    pub fn new(frequency: f64) -> Self {
+        Self { frequency, profile: PatternProfile::Smoothstep, phantom: PhantomData }
+    }
+
+    pub fn with_profile(mut self, profile: PatternProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+}
+
+impl<const DIM: usize, Metric> NoiseNode<DIM> for SpheresNode<DIM, Metric>
+where Metric: DistanceMetric {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let scaled_point = point * self.frequency;
+        let center = scaled_point.floor() + 0.5;
+
+        let distance = (scaled_point - center).magnitude::<Metric>();
+
+        self.profile.apply(distance.mul_add(-2.0, 1.0).max(0.0))
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{EuclideanMetric, RealPoint};
+    use crate::noise::{NoiseNode, PatternProfile};
+    use super::SpheresNode;
+
+    #[test]
+    fn linear_profile() {
+        let at = |x: f64, y: f64| RealPoint::<2>::new([x, y]);
+        let spheres = SpheresNode::<2, EuclideanMetric>::new(1.0).with_profile(PatternProfile::Linear);
+
+        assert_eq!(spheres.value_at(at(2.5, -3.5)), 1.0);
+        assert_eq!(spheres.value_at(at(2.75, -3.5)), 0.5);
+        assert_eq!(spheres.value_at(at(2.0, -3.0)), 0.0);
+    }
+}
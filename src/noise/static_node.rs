@@ -1,8 +1,9 @@
 use crate::geometry::RealPoint;
 use crate::random::StatelessRand;
 use crate::utils;
-use super::NoiseNode;
+use super::{grid, NoiseNode};
 
+#[derive(Clone)]
 pub struct StaticNode<const DIM: usize> {
     rng: StatelessRand,
     min: f64,
@@ -13,6 +14,15 @@ impl<const DIM: usize> StaticNode<DIM> {
     pub fn new(seed: u64, min: f64, max: f64) -> Self {
         Self { rng: StatelessRand::from_seed(seed), min, max }
     }
+
+    // Partial hashes of every column of a grid along x, still missing the other coordinates
+    fn column_digests(&self, num_values: u64, origin: f64, step: f64, start: usize, width: usize) -> Vec<u64> {
+        (start..(start + width)).map(|x| {
+            let x_bits = grid::grid_coordinate(origin, step, x).to_bits();
+
+            StatelessRand::hash_mix(self.rng.hash_start(num_values), x_bits)
+        }).collect()
+    }
 }
 
 impl NoiseNode<1> for StaticNode<1> {
@@ -32,6 +42,27 @@ impl NoiseNode<2> for StaticNode<2> {
 
         utils::f64_from_mantissa(hash, self.min, self.max)
     }
+
+    // Each column's x coordinate is mixed into the hash once, and reused by every row
+    fn fill_grid_section(&self, origin: RealPoint<2>, step: RealPoint<2>, start: [usize; 2], extent: [usize; 2], output: &mut [f64]) {
+        assert_eq!(output.len(), grid::num_points(extent));
+
+        if output.is_empty() {
+            return;
+        };
+
+        let columns = self.column_digests(2, origin[0], step[0], start[0], extent[0]);
+
+        for (y, row) in output.chunks_exact_mut(extent[0]).enumerate() {
+            let y_bits = grid::grid_coordinate(origin[1], step[1], start[1] + y).to_bits();
+
+            for (value, &digest) in row.iter_mut().zip(columns.iter()) {
+                let hash = StatelessRand::hash_finish(StatelessRand::hash_mix(digest, y_bits));
+
+                *value = utils::f64_from_mantissa(hash, self.min, self.max);
+            };
+        };
+    }
 }
 
 impl NoiseNode<3> for StaticNode<3> {
@@ -43,6 +74,28 @@ impl NoiseNode<3> for StaticNode<3> {
 
         utils::f64_from_mantissa(hash, self.min, self.max)
     }
+
+    fn fill_grid_section(&self, origin: RealPoint<3>, step: RealPoint<3>, start: [usize; 3], extent: [usize; 3], output: &mut [f64]) {
+        assert_eq!(output.len(), grid::num_points(extent));
+
+        if output.is_empty() {
+            return;
+        };
+
+        let columns = self.column_digests(3, origin[0], step[0], start[0], extent[0]);
+
+        for (row_index, row) in output.chunks_exact_mut(extent[0]).enumerate() {
+            let y_bits = grid::grid_coordinate(origin[1], step[1], start[1] + row_index % extent[1]).to_bits();
+            let z_bits = grid::grid_coordinate(origin[2], step[2], start[2] + row_index / extent[1]).to_bits();
+
+            for (value, &digest) in row.iter_mut().zip(columns.iter()) {
+                let digest = StatelessRand::hash_mix(StatelessRand::hash_mix(digest, y_bits), z_bits);
+                let hash = StatelessRand::hash_finish(digest);
+
+                *value = utils::f64_from_mantissa(hash, self.min, self.max);
+            };
+        };
+    }
 }
 
 impl<const DIM: usize> NoiseNode<DIM> for StaticNode<DIM> {
@@ -51,4 +104,8 @@ impl<const DIM: usize> NoiseNode<DIM> for StaticNode<DIM> {
 
         utils::f64_from_mantissa(hash, self.min, self.max)
     }
+
+    default fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        grid::fill_per_point(self, origin, step, start, extent, output)
+    }
 }
@@ -0,0 +1,62 @@
+use std::f64::consts::TAU;
+
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Waveform {
+    Sine,
+    Triangle,
+
+    // 1.0 for the first half of every period, 0.0 for the second
+    Square
+}
+
+// Parallel stripes across `wave_vector`, repeating once per 1 / |wave_vector| units along it.
+// Every waveform starts a period at 0.0, except the square wave, which starts at 1.0
+#[derive(Clone)]
+pub struct StripesNode<const DIM: usize> {
+    wave_vector: RealPoint<DIM>,
+    waveform: Waveform
+}
+
+impl<const DIM: usize> StripesNode<DIM> {
+    pub fn new(wave_vector: RealPoint<DIM>, waveform: Waveform) -> Self {
+        Self { wave_vector, waveform }
+    }
+}
+
+impl<const DIM: usize> NoiseNode<DIM> for StripesNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        let phase = point.dot_product(self.wave_vector).rem_euclid(1.0);
+
+        match self.waveform {
+            Waveform::Sine => (1.0 - (phase * TAU).cos()) / 2.0,
+            Waveform::Triangle => 1.0 - (phase.mul_add(2.0, -1.0)).abs(),
+            Waveform::Square => if phase < 0.5 { 1.0 } else { 0.0 }
+        }
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::NoiseNode;
+    use super::{StripesNode, Waveform};
+
+    #[test]
+    fn waveforms() {
+        let at = |x: f64, y: f64| RealPoint::<2>::new([x, y]);
+
+        for (waveform, expected) in [(Waveform::Sine, [0.0, 0.5, 1.0]), (Waveform::Triangle, [0.0, 0.5, 1.0]), (Waveform::Square, [1.0, 1.0, 0.0])] {
+            let stripes = StripesNode::new(at(0.0, 0.5), waveform);
+
+            for (y, expected) in [0.0, 0.5, 1.0].into_iter().zip(expected) {
+                assert!((stripes.value_at(at(3.0, y + 4.0)) - expected).abs() < 1e-12, "{:?} at {}", waveform, y);
+            };
+        };
+    }
+}
@@ -0,0 +1,112 @@
+use crate::geometry::RealPoint;
+use crate::utils;
+use super::NoiseNode;
+
+// Quantizes source values into terraces. Between two step heights the value eases in
+// quadratically, leaving a flat shelf at the lower step and a cliff up to the next; inverting
+// the terraces puts the shelf at the top, for mesas instead of plateaus
+#[derive(Clone)]
+pub struct TerraceNode<const DIM: usize, Source>
+where Source: NoiseNode<DIM> {
+    source: Source,
+    steps: Vec<f64>,
+    inverted: bool
+}
+
+impl<const DIM: usize, Source> TerraceNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    // Steps evenly spaced over [0.0, 1.0]
+    pub fn new(source: Source, num_steps: usize) -> Self {
+        assert!(num_steps >= 2, "terraces need at least two steps");
+
+        let steps = (0..num_steps).map(|step| (step as f64) / ((num_steps - 1) as f64)).collect();
+
+        Self::from_steps(source, steps)
+    }
+
+    pub fn from_steps(source: Source, mut steps: Vec<f64>) -> Self {
+        steps.sort_by(f64::total_cmp);
+        steps.dedup();
+
+        assert!(steps.len() >= 2, "terraces need at least two distinct steps");
+
+        Self { source, steps, inverted: false }
+    }
+
+    pub fn with_inversion(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+
+    // The terraced value and its slope at `value`
+    pub fn map(&self, value: f64) -> (f64, f64) {
+        let end = self.steps.partition_point(|&step| step <= value).clamp(1, self.steps.len() - 1);
+
+        let (mut lower, mut upper) = (self.steps[end - 1], self.steps[end]);
+        let mut bias = ((value - lower) / (upper - lower)).clamp(0.0, 1.0);
+
+        if self.inverted {
+            bias = 1.0 - bias;
+            (lower, upper) = (upper, lower);
+        };
+
+        let slope = if value > self.steps[0] && value < self.steps[self.steps.len() - 1] { 2.0 * bias } else { 0.0 };
+
+        (utils::lerp(bias * bias, lower, upper), slope)
+    }
+}
+
+impl<const DIM: usize, Source> NoiseNode<DIM> for TerraceNode<DIM, Source>
+where Source: NoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        self.map(self.source.value_at(point)).0
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (value, gradient) = self.source.value_and_gradient(point);
+        let (terrace_value, slope) = self.map(value);
+
+        (terrace_value, gradient * slope)
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::noise::ConstNode;
+    use super::TerraceNode;
+
+    #[test]
+    fn map() {
+        let terraces = TerraceNode::<1, _>::new(ConstNode::new(0.0), 3);
+
+        // Steps sit on their own values with a flat shelf, and values between them ease in
+        assert_eq!(terraces.map(0.0), (0.0, 0.0));
+        assert_eq!(terraces.map(0.5), (0.5, 0.0));
+        assert_eq!(terraces.map(1.0), (1.0, 0.0));
+        assert_eq!(terraces.map(0.25), (0.125, 1.0));
+        assert_eq!(terraces.map(0.75), (0.625, 1.0));
+
+        // Values past the ends clamp to the outer steps
+        assert_eq!(terraces.map(-1.0), (0.0, 0.0));
+        assert_eq!(terraces.map(2.0), (1.0, 0.0));
+
+        // Inverted terraces keep the steps, but put the cliff at the bottom and the shelf on top
+        let inverted = terraces.with_inversion(true);
+
+        assert_eq!(inverted.map(0.5).0, 0.5);
+        assert_eq!(inverted.map(0.25), (0.375, 1.0));
+        assert_eq!(inverted.map(0.75), (0.875, 1.0));
+        assert!(inverted.map(0.5 - 1e-9).1 < 1e-6);
+        assert_eq!(inverted.map(-1.0), (0.0, 0.0));
+        assert_eq!(inverted.map(2.0), (1.0, 0.0));
+
+        // Custom steps are sorted and deduplicated
+        let custom = TerraceNode::<1, _>::from_steps(ConstNode::new(0.0), vec![1.0, 0.2, 0.6, 0.2]);
+
+        assert!((custom.map(0.4).0 - 0.3).abs() < 1e-12);
+        assert!((custom.map(0.8).0 - 0.7).abs() < 1e-12);
+    }
+}
@@ -1,8 +1,9 @@
 use crate::geometry::RealPoint;
 use crate::random::StatelessRand;
 use crate::utils;
-use super::NoiseNode;
+use super::{grid, NoiseNode};
 
+#[derive(Clone)]
 pub struct TileNode<const DIM: usize> {
     rng: StatelessRand
 }
@@ -19,4 +20,47 @@ impl<const DIM: usize> NoiseNode<DIM> for TileNode<DIM> {
 
         utils::f64_from_mantissa(hash, 0.0, 1.0)
     }
+
+    // A row that lies in the same cells as the row before it is a copy of that row, and within a
+    // row a cell is only hashed again once the row crosses into the next one along x
+    fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        assert_eq!(output.len(), grid::num_points(extent));
+
+        if output.is_empty() {
+            return;
+        };
+
+        let width = extent[0];
+
+        let columns: Vec<f64> = (start[0]..(start[0] + width)).map(|x| {
+            grid::grid_coordinate(origin[0], step[0], x).floor()
+        }).collect();
+
+        let mut previous_cell: Option<RealPoint<DIM>> = None;
+
+        for row_start in (0..output.len()).step_by(width) {
+            let index = grid::section_index(start, extent, row_start);
+            let mut cell = grid::grid_point(origin, step, index).floor();
+
+            if previous_cell == Some(cell) {
+                output.copy_within((row_start - width)..row_start, row_start);
+                continue;
+            };
+
+            previous_cell = Some(cell);
+
+            let mut current_x = f64::NAN;
+            let mut cell_value = 0.0;
+
+            for (value, &cell_x) in output[row_start..(row_start + width)].iter_mut().zip(columns.iter()) {
+                if cell_x != current_x {
+                    cell[0] = cell_x;
+                    cell_value = self.value_at(cell);
+                    current_x = cell_x;
+                };
+
+                *value = cell_value;
+            };
+        };
+    }
 }
@@ -0,0 +1,41 @@
+use std::f64::consts::TAU;
+
+use crate::geometry::RealPoint;
+use super::NoiseNode;
+
+// Wraps the plane around a torus embedded in 4D, so a 4D source tiles seamlessly along both axes
+// of the 2D input. Each axis becomes a circle whose circumference is its period, keeping the
+// source's scale
+#[derive(Clone)]
+pub struct TorusNode<Source>
+where Source: NoiseNode<4> {
+    source: Source,
+    radii: [f64; 2],
+    period: [f64; 2]
+}
+
+impl<Source> TorusNode<Source>
+where Source: NoiseNode<4> {
+    pub fn new(source: Source, period: [f64; 2]) -> Self {
+        Self { source, radii: period.map(|period| period / TAU), period }
+    }
+
+    pub fn embed(&self, point: RealPoint<2>) -> RealPoint<4> {
+        let u = point[0] / self.period[0] * TAU;
+        let v = point[1] / self.period[1] * TAU;
+
+        RealPoint::<4>::new([
+            self.radii[0] * u.cos(),
+            self.radii[0] * u.sin(),
+            self.radii[1] * v.cos(),
+            self.radii[1] * v.sin()
+        ])
+    }
+}
+
+impl<Source> NoiseNode<2> for TorusNode<Source>
+where Source: NoiseNode<4> {
+    fn value_at(&self, point: RealPoint<2>) -> f64 {
+        self.source.value_at(self.embed(point))
+    }
+}
@@ -1,22 +1,31 @@
-use crate::geometry::{LinearMap, RealPoint};
+use crate::geometry::{AffineMap, RealPoint};
 use super::NoiseNode;
 
-pub struct TransformNode<'a, const DIM: usize, Source>
+#[derive(Clone)]
+pub struct TransformNode<const DIM: usize, Source>
 where Source: NoiseNode<DIM> {
-    source: &'a Source,
-    linear_map: LinearMap<DIM>
+    source: Source,
+    affine_map: AffineMap<DIM>
 }
 
-impl<'a, const DIM: usize, Source> TransformNode<'a, DIM, Source>
+impl<const DIM: usize, Source> TransformNode<DIM, Source>
 where Source: NoiseNode<DIM> {
-    pub fn new(source: &'a Source, linear_map: LinearMap<DIM>) -> Self {
-        Self { source, linear_map }
+    // Takes either a `LinearMap` or an `AffineMap`, so points can be translated, rotated and
+    // scaled in one step
+    pub fn new<Map: Into<AffineMap<DIM>>>(source: Source, map: Map) -> Self {
+        Self { source, affine_map: map.into() }
     }
 }
 
-impl<'a, const DIM: usize, Source> NoiseNode<DIM> for TransformNode<'a, DIM, Source>
+impl<const DIM: usize, Source> NoiseNode<DIM> for TransformNode<DIM, Source>
 where Source: NoiseNode<DIM> {
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
-        self.source.value_at(self.linear_map.apply(point))
+        self.source.value_at(self.affine_map.apply(point))
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let (value, gradient) = self.source.value_and_gradient(self.affine_map.apply(point));
+
+        (value, self.affine_map.linear_map().apply_transpose(gradient))
     }
 }
@@ -0,0 +1,241 @@
+use crate::geometry::RealPoint;
+use crate::random::StatelessRand;
+use crate::utils;
+use super::NoiseNode;
+use super::function::{lerp_vertices, lerp_vertices_with_gradient};
+
+// Cells are interpolated in buffers on the stack, sized for this many dimensions
+const MAX_DIM: usize = 8;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ValueInterpolation {
+    Linear,
+    Cosine,
+    Quintic,
+    Cubic
+}
+
+#[derive(Clone)]
+pub struct ValueNode<const DIM: usize> {
+    rng: StatelessRand,
+    interpolation: ValueInterpolation
+}
+
+impl<const DIM: usize> ValueNode<DIM> {
+    pub fn new(seed: u64, interpolation: ValueInterpolation) -> Self {
+        assert!(DIM <= MAX_DIM, "value noise supports at most {} dimensions", MAX_DIM);
+
+        Self { rng: StatelessRand::from_seed(seed), interpolation }
+    }
+
+    // Lattice vertices hash the same way `TileNode` hashes its cells, so both agree on a seed
+    pub fn noise_value_for(&self, vertex: RealPoint<DIM>) -> f64 {
+        let hash = self.rng.hash_bytes(vertex.as_bytes());
+
+        utils::f64_from_mantissa(hash, 0.0, 1.0)
+    }
+
+    // The interpolation weight along each axis at an offset into a cell, and its slope
+    fn biases(&self, offset: RealPoint<DIM>) -> (RealPoint<DIM>, RealPoint<DIM>) {
+        let mut biases = RealPoint::<DIM>::origin();
+        let mut slopes = RealPoint::<DIM>::origin();
+
+        for dim in 0..DIM {
+            let (bias, slope) = match self.interpolation {
+                ValueInterpolation::Cosine => (utils::cerp(offset[dim], 0.0, 1.0), utils::cerp_derivative(offset[dim], 0.0, 1.0)),
+                ValueInterpolation::Quintic => (utils::smoothstep(offset[dim]), utils::smoothstep_derivative(offset[dim])),
+                _ => (offset[dim], 1.0)
+            };
+
+            biases[dim] = bias;
+            slopes[dim] = slope;
+        };
+
+        (biases, slopes)
+    }
+
+    // Writes the values at the 2^N vertices of the cell holding `point` to the start of
+    // `output`, in the order of `VertexNeighborhood`, and returns that part of it
+    fn vertex_values<'a>(&self, point: RealPoint<DIM>, output: &'a mut [f64]) -> &'a mut [f64] {
+        let vertex_values = &mut output[..(1 << DIM)];
+
+        for (value, vertex) in vertex_values.iter_mut().zip(point.vertex_neighborhood()) {
+            *value = self.noise_value_for(vertex);
+        };
+
+        vertex_values
+    }
+
+    fn interpolate_vertices(&self, point: RealPoint<DIM>) -> f64 {
+        let mut buffer = [0.0; 1 << MAX_DIM];
+
+        let (biases, _) = self.biases(point - point.floor());
+
+        lerp_vertices(self.vertex_values(point, &mut buffer), biases)
+    }
+
+    // Catmull-Rom needs the vertices one step beyond the hypercube, so this samples the
+    // 4^N vertices at offsets -1, 0, 1 and 2 along every axis
+    fn interpolate_stencil(&self, point: RealPoint<DIM>) -> f64 {
+        let origin = point.floor();
+
+        // Catmull-Rom overshoots its control points slightly around sharp changes
+        self.collapse_stencil(origin, point - origin, DIM).clamp(0.0, 1.0)
+    }
+
+    // Collapses the stencil around `vertex` along its first `axes` axes. Recursing one axis at a
+    // time only ever holds four values per axis, rather than all 4^N
+    fn collapse_stencil(&self, vertex: RealPoint<DIM>, offset: RealPoint<DIM>, axes: usize) -> f64 {
+        if axes == 0 {
+            return self.noise_value_for(vertex);
+        };
+
+        let axis = axes - 1;
+
+        let [a, b, c, d] = [-1.0, 0.0, 1.0, 2.0].map(|step| {
+            let mut neighbor = vertex;
+
+            neighbor[axis] += step;
+            self.collapse_stencil(neighbor, offset, axis)
+        });
+
+        utils::cubic(offset[axis], a, b, c, d)
+    }
+
+    fn interpolate_vertices_with_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let mut buffer = [0.0; 1 << MAX_DIM];
+        let mut gradients = [RealPoint::<DIM>::origin(); 1 << MAX_DIM];
+
+        let (biases, slopes) = self.biases(point - point.floor());
+
+        let noise_values = self.vertex_values(point, &mut buffer);
+        let num_vertices = noise_values.len();
+
+        lerp_vertices_with_gradient(noise_values, &mut gradients[..num_vertices], biases, slopes)
+    }
+
+    fn interpolate_stencil_with_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        let origin = point.floor();
+
+        let (value, gradient) = self.collapse_stencil_with_gradient(origin, point - origin, DIM);
+
+        // Clamped values don't change with the point
+        if !(0.0..=1.0).contains(&value) {
+            return (value.clamp(0.0, 1.0), RealPoint::<DIM>::origin());
+        };
+
+        (value, gradient)
+    }
+
+    // `collapse_stencil`, carrying the gradient of every partial result along
+    fn collapse_stencil_with_gradient(&self, vertex: RealPoint<DIM>, offset: RealPoint<DIM>, axes: usize) -> (f64, RealPoint<DIM>) {
+        if axes == 0 {
+            return (self.noise_value_for(vertex), RealPoint::<DIM>::origin());
+        };
+
+        let axis = axes - 1;
+        let bias = offset[axis];
+
+        let [(a, ga), (b, gb), (c, gc), (d, gd)] = [-1.0, 0.0, 1.0, 2.0].map(|step| {
+            let mut neighbor = vertex;
+
+            neighbor[axis] += step;
+            self.collapse_stencil_with_gradient(neighbor, offset, axis)
+        });
+
+        let mut gradient = RealPoint::<DIM>::origin();
+
+        for dim in 0..DIM {
+            gradient[dim] = utils::cubic(bias, ga[dim], gb[dim], gc[dim], gd[dim]);
+        };
+
+        gradient[axis] += utils::cubic_derivative(bias, a, b, c, d);
+
+        (utils::cubic(bias, a, b, c, d), gradient)
+    }
+}
+
+impl<const DIM: usize> NoiseNode<DIM> for ValueNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        match self.interpolation {
+            ValueInterpolation::Cubic => self.interpolate_stencil(point),
+            _ => self.interpolate_vertices(point)
+        }
+    }
+
+    fn value_and_gradient(&self, point: RealPoint<DIM>) -> (f64, RealPoint<DIM>) {
+        match self.interpolation {
+            ValueInterpolation::Cubic => self.interpolate_stencil_with_gradient(point),
+            _ => self.interpolate_vertices_with_gradient(point)
+        }
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::NoiseNode;
+    use crate::random::StatefulRand;
+    use super::{ValueInterpolation, ValueNode};
+
+    const INTERPOLATIONS: [ValueInterpolation; 4] = [
+        ValueInterpolation::Linear, ValueInterpolation::Cosine, ValueInterpolation::Quintic, ValueInterpolation::Cubic
+    ];
+
+    fn assert_in_range<const DIM: usize>() {
+        let mut rng = StatefulRand::from_seed(1);
+
+        for interpolation in INTERPOLATIONS {
+            let node = ValueNode::<DIM>::new(1, interpolation);
+
+            for _ in 0..5000 {
+                let mut point = RealPoint::<DIM>::origin();
+
+                for coord in point.iter_mut() {
+                    *coord = rng.get_f64() * 200.0 - 100.0;
+                };
+
+                let value = node.value_at(point);
+
+                assert!((0.0..=1.0).contains(&value), "{:?} {}D gave {} at {}", interpolation, DIM, value, point);
+            };
+        };
+    }
+
+    fn assert_exact_at_vertices<const DIM: usize>() {
+        let mut rng = StatefulRand::from_seed(2);
+
+        for interpolation in INTERPOLATIONS {
+            let node = ValueNode::<DIM>::new(1, interpolation);
+
+            for _ in 0..100 {
+                let mut vertex = RealPoint::<DIM>::origin();
+
+                for coord in vertex.iter_mut() {
+                    *coord = (rng.get_f64() * 200.0 - 100.0).floor();
+                };
+
+                assert_eq!(node.value_at(vertex), node.noise_value_for(vertex), "{:?} {}D at {}", interpolation, DIM, vertex);
+            };
+        };
+    }
+
+    #[test]
+    fn in_range() {
+        assert_in_range::<1>();
+        assert_in_range::<2>();
+        assert_in_range::<3>();
+        assert_in_range::<4>();
+    }
+
+    #[test]
+    fn exact_at_vertices() {
+        assert_exact_at_vertices::<1>();
+        assert_exact_at_vertices::<2>();
+        assert_exact_at_vertices::<3>();
+        assert_exact_at_vertices::<4>();
+    }
+}
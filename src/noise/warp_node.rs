@@ -0,0 +1,70 @@
+use crate::geometry::RealPoint;
+use super::{NoiseNode, VectorNoiseNode};
+
+// Samples the source at points pushed around by a displacement field. Every extra iteration
+// displaces the point at which the displacement itself is sampled, so two iterations evaluate
+// `source(p + s * d(p + s * d(p)))`
+#[derive(Clone)]
+pub struct WarpNode<const DIM: usize, Source, Displacement>
+where Source: NoiseNode<DIM>, Displacement: VectorNoiseNode<DIM> {
+    source: Source,
+    displacement: Displacement,
+    strength: f64,
+    num_iterations: usize
+}
+
+impl<const DIM: usize, Source, Displacement> WarpNode<DIM, Source, Displacement>
+where Source: NoiseNode<DIM>, Displacement: VectorNoiseNode<DIM> {
+    pub fn new(source: Source, displacement: Displacement, strength: f64) -> Self {
+        Self { source, displacement, strength, num_iterations: 1 }
+    }
+
+    pub fn with_iterations(mut self, num_iterations: usize) -> Self {
+        self.num_iterations = num_iterations;
+        self
+    }
+
+    pub fn warp(&self, point: RealPoint<DIM>) -> RealPoint<DIM> {
+        let mut offset = RealPoint::<DIM>::origin();
+
+        for _ in 0..self.num_iterations {
+            offset = self.displacement.vector_at(point + offset) * self.strength;
+        };
+
+        point + offset
+    }
+}
+
+impl<const DIM: usize, Source, Displacement> NoiseNode<DIM> for WarpNode<DIM, Source, Displacement>
+where Source: NoiseNode<DIM>, Displacement: VectorNoiseNode<DIM> {
+    fn value_at(&self, point: RealPoint<DIM>) -> f64 {
+        self.source.value_at(self.warp(point))
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::RealPoint;
+    use crate::noise::{NoiseNode, PerlinNode, VectorNoiseNode};
+    use crate::utils;
+    use super::WarpNode;
+
+    #[test]
+    fn iterated_warp() {
+        let source = PerlinNode::<2>::new(1);
+        let displacement = [PerlinNode::<2>::new(2), PerlinNode::<2>::new(3)];
+
+        let point = RealPoint::<2>::new([1.3, -4.6]);
+
+        let once = displacement.vector_at(point) * 0.5;
+        let twice = displacement.vector_at(point + once) * 0.5;
+
+        let node = WarpNode::new(source.clone(), displacement.clone(), 0.5).with_iterations(2);
+
+        assert_eq!(node.value_at(point), source.value_at(point + twice));
+        assert_eq!(once[0], utils::unit_to_neg_unit(displacement[0].value_at(point)) * 0.5);
+    }
+}
@@ -1,24 +1,60 @@
+use std::any;
 use std::collections::HashMap;
-use std::cell::RefCell;
+use std::fmt;
 use std::marker::PhantomData;
 
-use crate::geometry::{EuclideanMetric, DistanceMetric, LatticePoint, RealPoint};
+use rand_distr::Poisson;
+
+use crate::geometry::{DistanceMetric, LatticePoint, RealPoint};
 use crate::random::{StatefulRand, StatelessRand};
 use crate::utils;
-use super::NoiseNode;
+use super::{grid, NoiseNode, PeriodicNode};
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum WorleyPaintMethod {
+    // Random value of the nearest feature point, identifying which region the point belongs to
     Value,
+
+    // Distance to the nearest, second and third nearest feature points
+    F1,
+    F2,
+    F3,
+
+    // Zero along the borders between cells
+    F2MinusF1,
+    F1TimesF2,
+    F1OverF2,
+
+    // The original name of `F1`, kept so existing graphs still build
+    #[deprecated(note = "use `WorleyPaintMethod::F1`")]
     Distance
 }
 
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum WorleyPointCount {
+    Fixed(usize),
+
+    // Mean number of points per cell, which must be positive
+    Poisson(f64)
+}
+
+// A `WorleyPointCount` ready to sample, so the Poisson distribution is only checked and built once
+#[derive(Copy, Clone)]
+enum PointSampler {
+    Fixed(usize),
+    Poisson(Poisson<f64>)
+}
+
+#[derive(Clone)]
 pub struct WorleyNode<const DIM: usize, Metric>
 where Metric: DistanceMetric {
     stateless_rng: StatelessRand,
-    stateful_rng: RefCell<StatefulRand>,
+    stateful_rng: StatefulRand,
     paint_method: WorleyPaintMethod,
-    hashmap: RefCell<HashMap<String, Vec<String>>>,
+    jitter: f64,
+    point_count: WorleyPointCount,
+    point_sampler: PointSampler,
+    period: Option<LatticePoint<DIM>>,
     phantom: PhantomData<Metric>
 }
 
@@ -27,70 +63,321 @@ where Metric: DistanceMetric {
     pub fn new(seed: u64, paint_method: WorleyPaintMethod) -> Self {
         Self {
             stateless_rng: StatelessRand::from_seed(seed),
-            stateful_rng: RefCell::new(StatefulRand::from_seed(seed)),
+            stateful_rng: StatefulRand::from_seed(seed),
             paint_method,
-            hashmap: RefCell::new(HashMap::new()),
+            jitter: 1.0,
+            point_count: WorleyPointCount::Fixed(1),
+            point_sampler: PointSampler::Fixed(1),
+            period: None,
             phantom: PhantomData
         }
     }
 
-    pub fn hypercube_seed_point(&self, point: RealPoint<DIM>, hypercube: LatticePoint<DIM>) -> (RealPoint<DIM>, u64) {
+    // A jitter of 0.0 places every feature point at the centre of its cell, 1.0 anywhere in it
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn with_point_count(mut self, point_count: WorleyPointCount) -> Self {
+        self.point_sampler = match point_count {
+            WorleyPointCount::Fixed(count) => PointSampler::Fixed(count),
+            WorleyPointCount::Poisson(mean) => {
+                assert!(mean > 0.0 && mean.is_finite(), "worley noise needs a positive, finite mean point count");
+
+                PointSampler::Poisson(Poisson::new(mean).unwrap())
+            }
+        };
+
+        self.point_count = point_count;
+        self
+    }
+
+    // Every cell gets its own ChaCha stream, copied from the seeded generator so that evaluation
+    // never mutates the node. Each feature point comes with its own id, hashed from the cell and
+    // its index within it
+    pub fn hypercube_seed_points(&self, hypercube: LatticePoint<DIM>) -> impl Iterator<Item = (RealPoint<DIM>, u64)> + '_ {
         let real_hypercube = hypercube.to_real_point();
-        let hash = self.stateless_rng.hash_bytes(hypercube.as_bytes());
+        let hash = self.stateless_rng.hash_bytes(self.wrap(hypercube).as_bytes());
 
-        let mut rng = self.stateful_rng.borrow_mut();
+        let mut rng = self.stateful_rng.clone();
 
         rng.set_word_pos(0);
         rng.set_stream(hash);
 
-        let mut coordinates = [0.0_f64; DIM];
+        let num_points = match self.point_sampler {
+            PointSampler::Fixed(count) => count,
+            PointSampler::Poisson(distribution) => rng.sample(distribution) as usize
+        };
+
+        (0..num_points).map(move |index| {
+            let mut coordinates = [0.0_f64; DIM];
+
+            for (dim, coord) in coordinates.iter_mut().enumerate() {
+                let fp_mod = utils::f64_from_mantissa(rng.next_u64(), 0.0, 1.0);
 
-        for dim in 0..DIM {
-            let fp_mod = utils::f64_from_mantissa(rng.next_u64(), 0.0, 1.0);
+                *coord = real_hypercube[dim] + utils::lerp(self.jitter, 0.5, fp_mod);
+            };
 
-            coordinates[dim] = real_hypercube[dim] + fp_mod;
+            (RealPoint::<DIM>::new(coordinates), self.stateless_rng.hash_2u64(hash, index as u64))
+        })
+    }
+
+    // Cells a whole period apart get the same feature points, each placed relative to its own cell
+    fn wrap(&self, mut hypercube: LatticePoint<DIM>) -> LatticePoint<DIM> {
+        if let Some(period) = self.period {
+            for (coord, &period) in hypercube.iter_mut().zip(period.iter()) {
+                *coord = coord.rem_euclid(period);
+            };
         };
 
-        (RealPoint::<DIM>::new(coordinates), hash)
+        hypercube
     }
 
-    pub fn display(&self) {
-        println!("{:?}", self.hashmap.borrow());
+    #[allow(deprecated)]
+    fn paint(&self, nearest: [(u64, f64); 3]) -> f64 {
+        let [(seed_value, f1), (_, f2), (_, f3)] = nearest;
+
+        // Feature points rarely sit further than a hypercube diagonal away, so distances are
+        // scaled by it and anything beyond is clamped
+        let max_distance = Metric::hypercube_diagonal_magnitude::<DIM>();
+
+        let value = match self.paint_method {
+            WorleyPaintMethod::Value => utils::f64_from_mantissa(seed_value, 0.0, 1.0),
+            WorleyPaintMethod::F1 | WorleyPaintMethod::Distance => f1 / max_distance,
+            WorleyPaintMethod::F2 => f2 / max_distance,
+            WorleyPaintMethod::F3 => f3 / max_distance,
+            WorleyPaintMethod::F2MinusF1 => (f2 - f1) / max_distance,
+            WorleyPaintMethod::F1TimesF2 => (f1 * f2) / (max_distance * max_distance),
+            WorleyPaintMethod::F1OverF2 => if f2 > 0.0 { f1 / f2 } else { 1.0 }
+        };
+
+        value.clamp(0.0, 1.0)
+    }
+}
+
+// Sparse point counts can leave some of the three nearest features unfilled, in which case they
+// count as maximally far
+const NO_FEATURES: [(u64, f64); 3] = [(0, f64::MAX); 3];
+
+// Keeps the three nearest (seed value, distance) pairs seen so far, sorted by distance
+fn insert_feature(nearest: &mut [(u64, f64); 3], seed_value: u64, distance: f64) {
+    if distance < nearest[2].1 {
+        nearest[2] = (seed_value, distance);
+
+        if nearest[2].1 < nearest[1].1 {
+            nearest.swap(1, 2);
+        };
+
+        if nearest[1].1 < nearest[0].1 {
+            nearest.swap(0, 1);
+        };
+    };
+}
+
+impl<const DIM: usize, Metric> fmt::Debug for WorleyNode<DIM, Metric>
+where Metric: DistanceMetric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WorleyNode")
+            .field("dimensions", &DIM)
+            .field("metric", &any::type_name::<Metric>())
+            .field("rng", &self.stateless_rng)
+            .field("paint_method", &self.paint_method)
+            .field("jitter", &self.jitter)
+            .field("point_count", &self.point_count)
+            .field("period", &self.period)
+            .finish()
     }
 }
 
 impl<const DIM: usize, Metric> NoiseNode<DIM> for WorleyNode<DIM, Metric>
 where Metric: DistanceMetric {
     fn value_at(&self, point: RealPoint<DIM>) -> f64 {
-        let mut candidates = point.to_lattice_point().neighbors_and_self().map(|hypercube| {
-            let (seed_point, seed_value) = self.hypercube_seed_point(point, hypercube);
-            let distance = (seed_point - point).magnitude::<Metric>();
+        let mut nearest = NO_FEATURES;
 
-            (seed_value, distance)
-        }).collect::<Vec<(u64, f64)>>();
+        for hypercube in point.to_lattice_point().neighbors_and_self() {
+            for (seed_point, seed_value) in self.hypercube_seed_points(hypercube) {
+                insert_feature(&mut nearest, seed_value, (seed_point - point).magnitude::<Metric>());
+            };
+        };
 
-        candidates.sort_by(|a, b| {
-            let lhs_distance = a.1;
-            let rhs_distance = b.1;
+        self.paint(nearest)
+    }
 
-            rhs_distance.partial_cmp(&lhs_distance).unwrap()
-        });
+    // Neighbouring points search mostly the same cells, so every cell's feature points are kept
+    // for as long as rows stay within the same cells along the other axes
+    fn fill_grid_section(&self, origin: RealPoint<DIM>, step: RealPoint<DIM>, start: [usize; DIM], extent: [usize; DIM], output: &mut [f64]) {
+        assert_eq!(output.len(), grid::num_points(extent));
 
-        let (seed_value, distance) = candidates.pop().unwrap();
+        let mut seed_points_cache: HashMap<LatticePoint<DIM>, Vec<(RealPoint<DIM>, u64)>> = HashMap::new();
+        let mut current_row_cell: Option<LatticePoint<DIM>> = None;
 
-        match self.paint_method {
-            WorleyPaintMethod::Value => {
-                utils::f64_from_mantissa(seed_value, 0.0, 1.0)
-            },
+        for (flat_index, value) in output.iter_mut().enumerate() {
+            let point = grid::grid_point(origin, step, grid::section_index(start, extent, flat_index));
+            let cell = point.to_lattice_point();
 
-            WorleyPaintMethod::Distance => {
-                let (_, other_distance) = candidates.pop().unwrap();
+            if flat_index % extent[0] == 0 {
+                let mut row_cell = cell;
 
-                let max_distance = Metric::hypercube_diagonal_magnitude::<DIM>();
-                let unbiased_value = (other_distance - distance) / max_distance;
+                row_cell[0] = 0;
 
-                distance
-            }
-        }
+                if current_row_cell != Some(row_cell) {
+                    seed_points_cache.clear();
+                    current_row_cell = Some(row_cell);
+                };
+            };
+
+            let mut nearest = NO_FEATURES;
+
+            for hypercube in cell.neighbors_and_self() {
+                let seed_points = seed_points_cache.entry(hypercube).or_insert_with(|| {
+                    self.hypercube_seed_points(hypercube).collect()
+                });
+
+                for &(seed_point, seed_value) in seed_points.iter() {
+                    insert_feature(&mut nearest, seed_value, (seed_point - point).magnitude::<Metric>());
+                };
+            };
+
+            *value = self.paint(nearest);
+        };
+    }
+}
+
+impl<const DIM: usize, Metric> PeriodicNode<DIM> for WorleyNode<DIM, Metric>
+where Metric: DistanceMetric {
+    fn with_period(mut self, period: [u32; DIM]) -> Self {
+        assert!(period.iter().all(|&cells| cells > 0), "periods must span at least one cell");
+
+        self.period = Some(LatticePoint::<DIM>::new(period.map(|cells| cells as i32)));
+        self
+    }
+}
+
+ //-------------------------------------------------------------------------------------------------
+// Tests
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::geometry::{EuclideanMetric, LatticePoint, ManhattanMetric, RealPoint};
+    use crate::noise::NoiseNode;
+    use crate::random::StatefulRand;
+    use super::{WorleyNode, WorleyPaintMethod, WorleyPointCount};
+
+    const PAINT_METHODS: [WorleyPaintMethod; 7] = [
+        WorleyPaintMethod::Value,
+        WorleyPaintMethod::F1,
+        WorleyPaintMethod::F2,
+        WorleyPaintMethod::F3,
+        WorleyPaintMethod::F2MinusF1,
+        WorleyPaintMethod::F1TimesF2,
+        WorleyPaintMethod::F1OverF2
+    ];
+
+    fn sample_points<const DIM: usize>() -> impl Iterator<Item = RealPoint<DIM>> {
+        let mut rng = StatefulRand::from_seed(1);
+
+        (0..500).map(move |_| {
+            let mut point = RealPoint::<DIM>::origin();
+
+            for coord in point.iter_mut() {
+                *coord = rng.get_f64() * 40.0 - 20.0;
+            };
+
+            point
+        })
+    }
+
+    #[test]
+    fn paint_methods_in_range() {
+        for paint_method in PAINT_METHODS {
+            let euclidean = WorleyNode::<2, EuclideanMetric>::new(1, paint_method);
+            let manhattan = WorleyNode::<3, ManhattanMetric>::new(1, paint_method);
+
+            for point in sample_points::<2>() {
+                let value = euclidean.value_at(point);
+
+                assert!((0.0..=1.0).contains(&value), "{:?} gave {} at {}", paint_method, value, point);
+            };
+
+            for point in sample_points::<3>() {
+                let value = manhattan.value_at(point);
+
+                assert!((0.0..=1.0).contains(&value), "{:?} gave {} at {}", paint_method, value, point);
+            };
+        };
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn ordered_distances() {
+        let f1 = WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::F1);
+        let f2 = WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::F2);
+        let f3 = WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::F3);
+        let distance = WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::Distance);
+
+        for point in sample_points::<2>() {
+            let (f1, f2, f3) = (f1.value_at(point), f2.value_at(point), f3.value_at(point));
+
+            assert!(f1 <= f2 && f2 <= f3, "{} {} {} at {}", f1, f2, f3, point);
+            assert_eq!(distance.value_at(point), f1);
+        };
+    }
+
+    #[test]
+    fn centred_points() {
+        let node = WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::F1).with_jitter(0.0);
+
+        for x in -3..3 {
+            let cell = LatticePoint::<2>::new([x, 2 * x + 1]);
+            let seed_points: Vec<(RealPoint<2>, u64)> = node.hypercube_seed_points(cell).collect();
+
+            assert_eq!(seed_points.len(), 1);
+            assert_eq!(seed_points[0].0, cell.to_real_point() + 0.5);
+        };
+
+        // Every point sits at the centre of a unit cell, so F1 is the distance to the nearest one
+        assert_eq!(node.value_at(RealPoint::<2>::new([0.5, 0.5])), 0.0);
+        assert!(node.value_at(RealPoint::<2>::new([1.0, 0.5])) > 0.0);
+    }
+
+    #[test]
+    fn point_counts() {
+        let fixed = WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::Value).with_point_count(WorleyPointCount::Fixed(4));
+        let poisson = WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::F1).with_point_count(WorleyPointCount::Poisson(2.5));
+
+        let cells = (0..400).map(|idx| LatticePoint::<2>::new([idx % 20, idx / 20]));
+        let mut total = 0;
+
+        for cell in cells {
+            let seed_points: Vec<(RealPoint<2>, u64)> = fixed.hypercube_seed_points(cell).collect();
+            let ids: HashSet<u64> = seed_points.iter().map(|&(_, id)| id).collect();
+
+            assert_eq!(seed_points.len(), 4);
+            assert_eq!(ids.len(), 4);
+
+            total += poisson.hypercube_seed_points(cell).count();
+        };
+
+        let mean = (total as f64) / 400.0;
+
+        assert!((mean - 2.5).abs() < 0.25, "mean point count {}", mean);
+
+        // With several points per cell, a single cell is split between several values
+        let values: HashSet<u64> = (0..100).map(|idx| {
+            let t = (idx as f64) / 100.0;
+
+            fixed.value_at(RealPoint::<2>::new([t, (t * 7.0).fract()])).to_bits()
+        }).collect();
+
+        assert!(values.len() > 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "positive, finite mean point count")]
+    fn empty_poisson_count() {
+        WorleyNode::<2, EuclideanMetric>::new(1, WorleyPaintMethod::F1).with_point_count(WorleyPointCount::Poisson(0.0));
     }
 }
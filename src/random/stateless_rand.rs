@@ -52,6 +52,20 @@ impl StatelessRand {
         StatelessRand::finalize(digest)
     }
 
+    // Hashes a tuple one value at a time, so tuples sharing their leading values can share that
+    // work. `hash_start(n)` must be followed by exactly `n` calls to `hash_mix` before `hash_finish`
+    pub fn hash_start(&self, num_values: u64) -> u64 {
+        self.prepare_seed(num_values * 8)
+    }
+
+    pub fn hash_mix(digest: u64, x: u64) -> u64 {
+        StatelessRand::mix_u64(digest, x)
+    }
+
+    pub fn hash_finish(digest: u64) -> u64 {
+        StatelessRand::finalize(digest)
+    }
+
     fn prepare_seed(&self, num_bytes: u64) -> u64 {
         self.seed.wrapping_add(PRIME_5).wrapping_add(num_bytes)
     }
@@ -103,4 +117,15 @@ mod test {
 
         assert_eq!(rand.hash_bytes(&bytes), rand.hash_3u64(0, 1, 2));
     }
+
+    #[test]
+    fn incremental_hash() {
+        let rand = StatelessRand::from_seed(1234);
+
+        let digest = StatelessRand::hash_mix(rand.hash_start(3), 7);
+        let digest = StatelessRand::hash_mix(digest, 8);
+        let digest = StatelessRand::hash_mix(digest, 9);
+
+        assert_eq!(StatelessRand::hash_finish(digest), rand.hash_3u64(7, 8, 9));
+    }
 }
@@ -32,6 +32,29 @@ pub fn cerp(bias: f64, lhs: f64, rhs: f64) -> f64 {
     (rhs - lhs).mul_add(bias, lhs)
 }
 
+// Derivative of `cerp` with respect to `bias`
+pub fn cerp_derivative(bias: f64, lhs: f64, rhs: f64) -> f64 {
+    (rhs - lhs) * (bias * PI).sin() * PI / 2.0
+}
+
+// Catmull-Rom cubic interpolation between `lhs` and `rhs`, shaped by their outer neighbours
+pub fn cubic(bias: f64, before: f64, lhs: f64, rhs: f64, after: f64) -> f64 {
+    let a = 1.5_f64.mul_add(lhs - rhs, 0.5 * (after - before));
+    let b = 2.5_f64.mul_add(-lhs, before) + 2.0_f64.mul_add(rhs, -0.5 * after);
+    let c = 0.5 * (rhs - before);
+
+    a.mul_add(bias, b).mul_add(bias, c).mul_add(bias, lhs)
+}
+
+// Derivative of `cubic` with respect to `bias`
+pub fn cubic_derivative(bias: f64, before: f64, lhs: f64, rhs: f64, after: f64) -> f64 {
+    let a = 1.5_f64.mul_add(lhs - rhs, 0.5 * (after - before));
+    let b = 2.5_f64.mul_add(-lhs, before) + 2.0_f64.mul_add(rhs, -0.5 * after);
+    let c = 0.5 * (rhs - before);
+
+    (3.0 * a).mul_add(bias, 2.0 * b).mul_add(bias, c)
+}
+
 // All f32/f64 values in the range [1.0, 2.0) differ only in the mantissa
 pub fn f32_from_mantissa(mantissa: u32, min: f32, max: f32) -> f32 {
     let float = f32::from_bits(F32_ONE_BITS ^ (mantissa >> 9));
@@ -54,6 +77,11 @@ pub fn smoothstep(x: f64) -> f64 {
     x.powi(3) * b
 }
 
+// Returns 30x^4 - 60x^3 + 30x^2
+pub fn smoothstep_derivative(x: f64) -> f64 {
+    30.0 * (x * (x - 1.0)).powi(2)
+}
+
 // Extension of `smoothstep` that works with negative numbers
 // Maps [-1.0, 1.0] -> [-1.0, 1.0]
 pub fn neg_smoothstep(x: f64) -> f64 {
@@ -65,11 +93,27 @@ pub fn neg_smoothstep(x: f64) -> f64 {
     x.powi(3).mul_add(b, -1.0)
 }
 
+pub fn neg_smoothstep_derivative(x: f64) -> f64 {
+    smoothstep_derivative(x.mul_add(0.5, 0.5))
+}
+
 // Maps [0.0, 1.0] -> [0.0, 1.0]
 pub fn sigmoid(beta: f64, x: f64) -> f64 {
     1.0 / (1.0 + (x / (1.0 - x)).powf(beta))
 }
 
+// Written in terms of `sigmoid` itself, which stays finite for negative betas. The slope is taken
+// as flat at the ends of the range, where it's either zero or unbounded
+pub fn sigmoid_derivative(beta: f64, x: f64) -> f64 {
+    if x <= 0.0 || x >= 1.0 {
+        return 0.0;
+    };
+
+    let value = sigmoid(beta, x);
+
+    -beta * value * (1.0 - value) / (x * (1.0 - x))
+}
+
 // Extension of `sigmoid` that works with negative numbers
 // Maps [-1.0, 1.0] -> [-1.0, 1.0]
 pub fn neg_sigmoid(beta: f64, x: f64) -> f64 {